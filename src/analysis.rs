@@ -0,0 +1,221 @@
+use super::*;
+
+/// Computes the tangent of a 1D homotopy at `s` using a central difference.
+///
+/// Near the boundaries, the samples are clamped to `[0, 1]`, giving a one-sided difference.
+pub fn tangent<H, X>(h: &H, x: X, s: f64, eps: f64) -> H::Y
+    where H: Homotopy<X>, H::Y: Vector, X: Clone
+{
+    let a = (s - eps).max(0.0);
+    let b = (s + eps).min(1.0);
+    h.h(x.clone(), b).sub_vec(h.h(x, a)).scale(1.0 / (b - a))
+}
+
+/// Samples a 1D homotopy at `n + 1` evenly spaced scalars, with the tangent at each sample.
+///
+/// Each tangent is a central difference of the neighboring samples,
+/// one-sided at the ends, which is the same as `tangent` with `eps = 1.0 / n`.
+/// Since the differences reuse the samples, `h` is evaluated once per sample
+/// instead of three times for separate `tangent` calls.
+/// This is useful for orienting sprites or ribbons along a path.
+/// Panics if `n` is zero.
+pub fn sample_with_tangent<H, X>(h: &H, x: X, n: u32) -> Vec<(H::Y, H::Y)>
+    where H: Homotopy<X>, H::Y: Vector + Clone, X: Clone
+{
+    assert!(n >= 1, "Expected at least one segment");
+    let spacing = 1.0 / n as f64;
+    let points: Vec<H::Y> = (0..n + 1).map(|i| h.h(x.clone(), i as f64 / n as f64)).collect();
+    let last = n as usize;
+    (0..last + 1).map(|i| {
+        let (a, b) = (i.saturating_sub(1), (i + 1).min(last));
+        let tangent = points[b].clone().sub_vec(points[a].clone())
+            .scale(1.0 / ((b - a) as f64 * spacing));
+        (points[i].clone(), tangent)
+    }).collect()
+}
+
+/// Computes the acceleration of a 1D homotopy at `s`, using a central second difference.
+///
+/// This is `(h(s + eps) - 2 h(s) + h(s - eps)) / eps²`.
+/// Near the boundaries, the center of the difference is clamped to `[eps, 1 - eps]`.
+pub fn acceleration<H, X>(h: &H, x: X, s: f64, eps: f64) -> H::Y
+    where H: Homotopy<X>, H::Y: Vector, X: Clone
+{
+    let s = s.max(eps).min(1.0 - eps);
+    let a = h.h(x.clone(), s - eps);
+    let b = h.h(x.clone(), s);
+    let c = h.h(x, s + eps);
+    c.sub_vec(b.scale(2.0)).add_vec(a).scale(1.0 / (eps * eps))
+}
+
+/// Computes the curvature of a 2D curve at `s`.
+///
+/// This is `|v × a| / |v|³` where `v` is the tangent and `a` is the acceleration.
+pub fn curvature<H, X>(h: &H, x: X, s: f64, eps: f64) -> f64
+    where H: Homotopy<X, Y = [f64; 2]>, X: Clone
+{
+    let v = tangent(h, x.clone(), s, eps);
+    let a = acceleration(h, x, s, eps);
+    let len = (v[0] * v[0] + v[1] * v[1]).sqrt();
+    (v[0] * a[1] - v[1] * a[0]).abs() / (len * len * len)
+}
+
+/// Computes the signed area enclosed by a closed 2D curve, using `n` samples.
+///
+/// The area is positive when the curve winds counter-clockwise and negative when clockwise.
+pub fn signed_area<H, X>(h: &H, n: u32) -> f64
+    where H: Homotopy<X, Y = [f64; 2]>, X: Default
+{
+    let mut sum = 0.0;
+    let mut a = h.hu(0.0);
+    for i in 1..n + 1 {
+        let b = if i == n {h.hu(0.0)} else {h.hu(i as f64 / n as f64)};
+        sum += a[0] * b[1] - b[0] * a[1];
+        a = b;
+    }
+    0.5 * sum
+}
+
+/// Returns `true` if a closed 2D curve winds counter-clockwise, using `n` samples.
+pub fn is_ccw<H, X>(h: &H, n: u32) -> bool
+    where H: Homotopy<X, Y = [f64; 2]>, X: Default
+{
+    signed_area(h, n) > 0.0
+}
+
+/// Finds the scalar where a 2D curve comes closest to the point `p`.
+///
+/// The curve is first sampled at `n + 1` evenly spaced scalars.
+/// Then the interval around the best sample is narrowed by `refine` steps of ternary search.
+pub fn closest_param<H, X>(h: &H, x: X, p: [f64; 2], n: u32, refine: u32) -> f64
+    where H: Homotopy<X, Y = [f64; 2]>, X: Clone
+{
+    let dist = |s: f64| {
+        let q = h.h(x.clone(), s);
+        (q[0] - p[0]).powi(2) + (q[1] - p[1]).powi(2)
+    };
+    let mut best = 0.0;
+    let mut best_dist = dist(0.0);
+    for i in 1..n + 1 {
+        let s = i as f64 / n as f64;
+        let d = dist(s);
+        if d < best_dist {
+            best = s;
+            best_dist = d;
+        }
+    }
+    let step = 1.0 / n as f64;
+    let mut a = (best - step).max(0.0);
+    let mut b = (best + step).min(1.0);
+    for _ in 0..refine {
+        let m1 = a + (b - a) / 3.0;
+        let m2 = b - (b - a) / 3.0;
+        if dist(m1) < dist(m2) {b = m2} else {a = m1}
+    }
+    let s = 0.5 * (a + b);
+    if dist(s) < best_dist {s} else {best}
+}
+
+/// Folds over `n + 1` evenly spaced samples of a 1D homotopy, without collecting them.
+///
+/// The closure receives the accumulator, the scalar and the output of each sample.
+/// This supports computing sums, extrema or integrals in a single pass.
+pub fn fold_samples<H, X, B, F>(h: &H, x: X, n: u32, init: B, mut f: F) -> B
+    where H: Homotopy<X>, X: Clone, F: FnMut(B, f64, H::Y) -> B
+{
+    let mut acc = init;
+    for i in 0..n + 1 {
+        let s = i as f64 / n as f64;
+        acc = f(acc, s, h.h(x.clone(), s));
+    }
+    acc
+}
+
+/// Computes a flow field of a 2D homotopy at a fixed scalar `s`.
+///
+/// Returns the numeric tangent at the center of each grid cell over `[0, 1]²`,
+/// in row-major order with `res[0]` cells per row.
+/// This can be used to advect particles along the homotopy.
+pub fn flow_field<H>(h: &H, res: [u32; 2], s: f64, eps: f64) -> Vec<[f64; 2]>
+    where H: Homotopy<[f64; 2], Y = [f64; 2]>
+{
+    let mut field = Vec::with_capacity((res[0] * res[1]) as usize);
+    for j in 0..res[1] {
+        for i in 0..res[0] {
+            let x = [(i as f64 + 0.5) / res[0] as f64, (j as f64 + 0.5) / res[1] as f64];
+            field.push(tangent(h, x, s, eps));
+        }
+    }
+    field
+}
+
+/// Returns `count` points along a curve, spaced equally by arc length instead of by scalar.
+///
+/// The arc length is approximated by a dense polyline,
+/// and each point is evaluated on the curve at the scalar found by inverting it.
+/// The first and last points are `f` and `g`.
+/// Panics if `count` is less than two.
+pub fn resample_uniform<H, X>(h: &H, x: X, count: u32) -> Vec<H::Y>
+    where H: Homotopy<X>, H::Y: Vector + AsRef<[f64]> + Clone, X: Clone
+{
+    assert!(count >= 2, "Expected at least two points");
+    let n = (count * 16).max(256);
+    let mut cumulative = Vec::with_capacity(n as usize + 1);
+    cumulative.push(0.0);
+    let mut prev = h.f(x.clone());
+    for i in 1..n + 1 {
+        let p = h.h(x.clone(), i as f64 / n as f64);
+        let d = p.clone().sub_vec(prev);
+        let len = d.as_ref().iter().map(|v| v * v).sum::<f64>().sqrt();
+        cumulative.push(cumulative[i as usize - 1] + len);
+        prev = p;
+    }
+
+    let total = cumulative[n as usize];
+    let mut points = Vec::with_capacity(count as usize);
+    points.push(h.f(x.clone()));
+    let mut j = 0;
+    for k in 1..count - 1 {
+        let target = total * k as f64 / (count - 1) as f64;
+        while cumulative[j + 1] < target {j += 1}
+        let seg = cumulative[j + 1] - cumulative[j];
+        let t = if seg > 0.0 {(target - cumulative[j]) / seg} else {0.0};
+        points.push(h.h(x.clone(), (j as f64 + t) / n as f64));
+    }
+    points.push(h.g(x));
+    points
+}
+
+/// Finds the self-intersections of a 2D curve, sampled as a polyline of `n` segments.
+///
+/// Returns each intersection point with the two scalars where the curve passes through it,
+/// the smaller scalar first.
+/// Segments next to each other are not compared, and neither are the first and last segment
+/// when the curve is closed, such that the shared endpoints are not reported.
+/// Crossings that fall exactly on a sample point may be missed, so `n` should be chosen
+/// to not align with known features of the curve.
+pub fn self_intersections<H, X>(h: &H, n: u32) -> Vec<([f64; 2], f64, f64)>
+    where H: Homotopy<X, Y = [f64; 2]>, X: Default
+{
+    let cross = |a: [f64; 2], b: [f64; 2]| a[0] * b[1] - a[1] * b[0];
+    let points: Vec<[f64; 2]> = (0..n + 1).map(|i| h.hu(i as f64 / n as f64)).collect();
+    let closed = points[0] == points[n as usize];
+    let n = n as usize;
+    let mut res = vec![];
+    for i in 0..n {
+        for j in i + 2..n {
+            if closed && i == 0 && j == n - 1 {continue};
+            let (p, r) = (points[i], points[i + 1].sub_vec(points[i]));
+            let (q, d) = (points[j], points[j + 1].sub_vec(points[j]));
+            let denom = cross(r, d);
+            if denom.abs() < 1e-15 {continue};
+            let t = cross(q.sub_vec(p), d) / denom;
+            let u = cross(q.sub_vec(p), r) / denom;
+            if (0.0..1.0).contains(&t) && (0.0..1.0).contains(&u) {
+                let (s0, s1) = ((i as f64 + t) / n as f64, (j as f64 + u) / n as f64);
+                res.push((p.add_vec(r.scale(t)), s0, s1));
+            }
+        }
+    }
+    res
+}
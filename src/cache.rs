@@ -0,0 +1,60 @@
+use super::*;
+
+/// Memoizes recently evaluated results of a homotopy.
+///
+/// This speeds up scrubbing where the same input and scalar recur,
+/// e.g. for homotopies that are expensive to evaluate.
+/// The cache keeps the `capacity` most recently used results and evicts the least recent.
+pub struct CachedHomotopy<T, X, S, Y> {
+    homotopy: T,
+    capacity: usize,
+    // Ordered from most recently used to least recently used.
+    entries: Vec<(X, S, Y)>,
+}
+
+impl<T, X, S, Y> CachedHomotopy<T, X, S, Y> {
+    /// Returns a reference to the cached homotopy.
+    pub fn get_ref(&self) -> &T {&self.homotopy}
+
+    /// Returns a mutable reference to the cached homotopy.
+    ///
+    /// This clears the cache, since the results might change.
+    pub fn homotopy_mut(&mut self) -> &mut T {
+        self.entries.clear();
+        &mut self.homotopy
+    }
+
+    /// Returns the cached homotopy, discarding the cache.
+    pub fn into_inner(self) -> T {self.homotopy}
+}
+
+impl<T, X, S, Y> CachedHomotopy<T, X, S, Y>
+    where T: Homotopy<X, S, Y = Y>, X: Clone + PartialEq, S: Clone + PartialEq, Y: Clone
+{
+    /// Creates a new cache holding up to `capacity` results.
+    pub fn new(homotopy: T, capacity: usize) -> Self {
+        CachedHomotopy {homotopy, capacity, entries: Vec::with_capacity(capacity)}
+    }
+
+    /// Call `h`, reusing a cached result when available.
+    pub fn h_cached(&mut self, x: X, s: S) -> Y {
+        if let Some(i) = self.entries.iter().position(|e| e.0 == x && e.1 == s) {
+            let entry = self.entries.remove(i);
+            let y = entry.2.clone();
+            self.entries.insert(0, entry);
+            return y;
+        }
+        let y = self.homotopy.h(x.clone(), s.clone());
+        if self.capacity > 0 {
+            self.entries.truncate(self.capacity - 1);
+            self.entries.insert(0, (x, s, y.clone()));
+        }
+        y
+    }
+
+    /// Returns the number of cached results.
+    pub fn len(&self) -> usize {self.entries.len()}
+
+    /// Returns `true` if there are no cached results.
+    pub fn is_empty(&self) -> bool {self.entries.is_empty()}
+}
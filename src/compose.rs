@@ -89,3 +89,30 @@ impl<X, H1, H2> Homotopy<X, [f64; 5]> for Compose<H1, H2, [f64; 4], f64>
         self.h2.h(self.h1.h(x, [s[0], s[1], s[2], s[3]]), s[4])
     }
 }
+
+/// Cross-fades between two chains of homotopies, e.g. the diagonals of two compositions.
+///
+/// At each scalar, both chains are evaluated and their outputs blended,
+/// with the weight of the second chain given by the scalar itself.
+/// Unlike `Blend`, which uses a fixed weight, this fades from the first chain to the second,
+/// so the chains do not need shared endpoints:
+/// `f` is the blended endpoint at weight `0.0`, which is `f` of the first chain,
+/// and `g` is the blended endpoint at weight `1.0`, which is `g` of the second chain.
+#[derive(Copy, Clone)]
+pub struct ChainBlend<A, B>(pub A, pub B);
+
+impl<X, A, B> Homotopy<X> for ChainBlend<A, B>
+    where A: Homotopy<X>, B: Homotopy<X, Y = A::Y>,
+          A::Y: Mul<f64, Output = A::Y> + Add<Output = A::Y>,
+          X: Clone
+{
+    type Y = A::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.0.f(x)}
+    fn g(&self, x: X) -> Self::Y {self.1.g(x)}
+    fn h(&self, x: X, s: f64) -> Self::Y {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.f(x)} else if s == 1.0 {return self.g(x)};
+        self.0.h(x.clone(), s) * (1.0 - s) + self.1.h(x, s) * s
+    }
+}
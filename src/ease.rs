@@ -0,0 +1,287 @@
+use super::*;
+
+/// Reparametrizes a scalar in the unit interval.
+///
+/// Every variant maps `0.0` to `0.0` and `1.0` to `1.0`,
+/// so applying it to the scalar of a homotopy preserves the homotopy constraints.
+/// An exponent of `0` is treated like `1`, which is linear, to keep this property.
+#[derive(Copy, Clone)]
+pub enum Easing {
+    /// Leaves the scalar unchanged.
+    Linear,
+    /// Starts slow, using `s^n`.
+    EaseIn(u32),
+    /// Ends slow, using `1 - (1 - s)^n`.
+    EaseOut(u32),
+    /// Starts and ends slow, using ease-in for the first half and ease-out for the second half.
+    EaseInOut(u32),
+    /// Starts and ends slow, using the Hermite polynomial `3s² - 2s³`.
+    SmoothStep,
+    /// Starts and ends slow with zero first and second derivatives,
+    /// using Perlin's quintic `6s⁵ - 15s⁴ + 10s³`.
+    SmootherStep,
+}
+
+impl Easing {
+    /// Reparametrizes the scalar `s`.
+    pub fn apply(&self, s: f64) -> f64 {
+        match *self {
+            Easing::Linear => s,
+            Easing::EaseIn(n) => s.powi(n.max(1) as i32),
+            Easing::EaseOut(n) => 1.0 - (1.0 - s).powi(n.max(1) as i32),
+            Easing::EaseInOut(n) => {
+                let n = n.max(1);
+                if s < 0.5 {0.5 * (2.0 * s).powi(n as i32)}
+                else {1.0 - 0.5 * (2.0 * (1.0 - s)).powi(n as i32)}
+            }
+            Easing::SmoothStep => s * s * (3.0 - 2.0 * s),
+            Easing::SmootherStep => s * s * s * (s * (s * 6.0 - 15.0) + 10.0),
+        }
+    }
+}
+
+/// Eases each output axis of a 2D homotopy independently.
+///
+/// The wrapped homotopy is evaluated once per axis, at the scalar produced by that axis' easing,
+/// and the results are recombined by taking the x-component from the first evaluation
+/// and the y-component from the second.
+#[derive(Copy, Clone)]
+pub struct AxisEase<T>(pub T, pub [Easing; 2]);
+
+impl<X, T> Homotopy<X> for AxisEase<T>
+    where T: Homotopy<X, Y = [f64; 2]>, X: Clone
+{
+    type Y = [f64; 2];
+
+    fn f(&self, x: X) -> Self::Y {self.0.f(x)}
+    fn g(&self, x: X) -> Self::Y {self.0.g(x)}
+    fn h(&self, x: X, s: f64) -> Self::Y {
+        let a = self.0.h(x.clone(), self.1[0].apply(s));
+        let b = self.0.h(x, self.1[1].apply(s));
+        [a[0], b[1]]
+    }
+}
+
+/// Eases each scalar axis of a 3D homotopy independently.
+///
+/// This staggers the timing of the three dimensions, e.g. of a `Cube` animation.
+#[derive(Copy, Clone)]
+pub struct CubeEase<T>(pub T, pub [Easing; 3]);
+
+impl<X, T> Homotopy<X, [f64; 3]> for CubeEase<T>
+    where T: Homotopy<X, [f64; 3]>
+{
+    type Y = T::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.0.f(x)}
+    fn g(&self, x: X) -> Self::Y {self.0.g(x)}
+    fn h(&self, x: X, s: [f64; 3]) -> Self::Y {
+        self.0.h(x, [self.1[0].apply(s[0]), self.1[1].apply(s[1]), self.1[2].apply(s[2])])
+    }
+}
+
+/// Reparametrizes the scalar of a homotopy using a precomputed table.
+///
+/// The table holds the reparametrized values for evenly spaced scalars over `[0, 1]`,
+/// linearly interpolated between entries.
+/// This avoids calling an easing function for every sample.
+#[derive(Clone)]
+pub struct TableReparam<T>(pub T, pub Vec<f64>);
+
+impl<T> TableReparam<T> {
+    /// Creates a new table reparametrization.
+    ///
+    /// Panics if the table has less than two entries, is not monotonically increasing,
+    /// or does not start at `0.0` and end at `1.0`.
+    pub fn new(inner: T, table: Vec<f64>) -> TableReparam<T> {
+        assert!(table.len() >= 2, "Expected at least two entries");
+        assert!(table[0] == 0.0 && table[table.len() - 1] == 1.0,
+                "Expected table to span from `0.0` to `1.0`");
+        assert!(table.windows(2).all(|w| w[0] <= w[1]), "Expected monotonic table");
+        TableReparam(inner, table)
+    }
+
+    /// Creates a table by sampling an easing at `n + 1` evenly spaced scalars.
+    pub fn from_easing(inner: T, easing: Easing, n: u32) -> TableReparam<T> {
+        let table = (0..n + 1).map(|i| easing.apply(i as f64 / n as f64)).collect();
+        TableReparam::new(inner, table)
+    }
+}
+
+impl<X, T> Homotopy<X> for TableReparam<T>
+    where T: Homotopy<X>
+{
+    type Y = T::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.0.f(x)}
+    fn g(&self, x: X) -> Self::Y {self.0.g(x)}
+    fn h(&self, x: X, s: f64) -> Self::Y {self.0.h(x, sample_table(&self.1, s))}
+}
+
+/// Blends between two easings.
+///
+/// The input is the scalar being reparametrized and the scalar of the homotopy blends
+/// between the reparametrizations, such that `f` is the first easing and `g` is the second.
+#[derive(Copy, Clone)]
+pub struct EaseBlend(pub Easing, pub Easing);
+
+impl Homotopy<f64> for EaseBlend {
+    type Y = f64;
+
+    fn f(&self, x: f64) -> f64 {self.0.apply(x)}
+    fn g(&self, x: f64) -> f64 {self.1.apply(x)}
+    fn h(&self, x: f64, s: f64) -> f64 {Lerp(self.f(x), self.g(x)).hu(s)}
+}
+
+/// Reparametrizes the scalar of a homotopy using another homotopy as a time curve.
+///
+/// The time curve maps the unit interval to itself, e.g. for slow-in/slow-out,
+/// such that easing curves can be composed as homotopies instead of closures.
+/// It should map `0.0` to `0.0` and `1.0` to `1.0` to preserve the homotopy constraints.
+#[derive(Copy, Clone)]
+pub struct Retime<T, C>(pub T, pub C);
+
+impl<X, T, C> Homotopy<X> for Retime<T, C>
+    where T: Homotopy<X>, C: Homotopy<(), Y = f64>
+{
+    type Y = T::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.0.f(x)}
+    fn g(&self, x: X) -> Self::Y {self.0.g(x)}
+    fn h(&self, x: X, s: f64) -> Self::Y {self.0.h(x, self.1.hu(s))}
+}
+
+/// Rolls a displayed number from one value to another, like an odometer.
+///
+/// The fields are the start number, the end number and the snap window.
+/// The number is eased with `Easing::SmoothStep`.
+/// Within the snap window at each end of the scalar range,
+/// the displayed value is rounded to the nearest integer, so counters settle on whole numbers,
+/// while it rolls smoothly in between.
+/// `f` and `g` return the exact endpoint numbers.
+#[derive(Copy, Clone)]
+pub struct Odometer(pub f64, pub f64, pub f64);
+
+impl Homotopy<()> for Odometer {
+    type Y = f64;
+
+    fn f(&self, _: ()) -> f64 {self.0}
+    fn g(&self, _: ()) -> f64 {self.1}
+    fn h(&self, _: (), s: f64) -> f64 {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.0} else if s == 1.0 {return self.1};
+        let v = Lerp(self.0, self.1).hu(Easing::SmoothStep.apply(s));
+        if s < self.2 || s > 1.0 - self.2 {v.round()} else {v}
+    }
+}
+
+/// Reparametrizes the scalar of a homotopy with an easing.
+///
+/// Since every easing maps `0.0` to `0.0` and `1.0` to `1.0`, the homotopy constraints hold.
+#[derive(Copy, Clone)]
+pub struct Eased<T>(pub T, pub Easing);
+
+impl<X, T> Homotopy<X> for Eased<T>
+    where T: Homotopy<X>
+{
+    type Y = T::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.0.f(x)}
+    fn g(&self, x: X) -> Self::Y {self.0.g(x)}
+    fn h(&self, x: X, s: f64) -> Self::Y {self.0.h(x, self.1.apply(s))}
+}
+
+/// Interpolates between two endpoints with the scalar remapped by an easing.
+///
+/// For example, `Ease(a, b, Easing::EaseIn(2))` is a quadratic ease-in
+/// and `Ease(a, b, Easing::EaseInOut(4))` is a quartic ease-in-out.
+/// With `Easing::Linear`, this is the same as `Lerp`.
+#[derive(Copy, Clone)]
+pub struct Ease<X>(pub X, pub X, pub Easing);
+
+impl<Y> Homotopy<()> for Ease<Y>
+    where Y: Mul<f64, Output = Y> + Add<Output = Y> + Clone
+{
+    type Y = Y;
+
+    fn f(&self, _: ()) -> Y {self.0.clone()}
+    fn g(&self, _: ()) -> Y {self.1.clone()}
+    fn h(&self, _: (), s: f64) -> Y {Lerp(self.0.clone(), self.1.clone()).h((), self.2.apply(s))}
+}
+
+/// Holds the start of a homotopy for a delay, then plays it over the rest of the scalar range.
+///
+/// The delay should be in `[0, 1)`, which `Delay::new` checks.
+/// Before the delay, the output is `f`, and after it the scalar is rescaled to `[0, 1]`,
+/// so the homotopy still reaches `g` exactly at `1.0`.
+/// This is useful for staggering entrances of several animations.
+#[derive(Copy, Clone)]
+pub struct Delay<T>(pub T, pub f64);
+
+impl<T> Delay<T> {
+    /// Creates a new delay.
+    ///
+    /// Panics if the delay is not in `[0, 1)`.
+    pub fn new(inner: T, delay: f64) -> Delay<T> {
+        assert!(delay >= 0.0 && delay < 1.0, "Expected delay in `[0, 1)`");
+        Delay(inner, delay)
+    }
+}
+
+impl<X, T> Homotopy<X> for Delay<T>
+    where T: Homotopy<X>
+{
+    type Y = T::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.0.f(x)}
+    fn g(&self, x: X) -> Self::Y {self.0.g(x)}
+    fn h(&self, x: X, s: f64) -> Self::Y {
+        if s < self.1 {self.0.f(x)} else {self.0.h(x, (s - self.1) / (1.0 - self.1))}
+    }
+}
+
+/// Reparametrizes the scalar of a homotopy by a warp function.
+///
+/// The warp must map `0.0` to `0.0` and `1.0` to `1.0`, such that the homotopy still
+/// starts at `f` and ends at `g`. This is checked in debug builds.
+/// Unlike `Eased`, the warp can be any function, e.g. a closure or a 1D homotopy.
+#[derive(Copy, Clone)]
+pub struct TimeWarp<T, F> {
+    /// The homotopy to reparametrize.
+    pub inner: T,
+    /// The function that remaps the scalar.
+    pub warp: F,
+}
+
+impl<X, T, F> Homotopy<X> for TimeWarp<T, F>
+    where T: Homotopy<X>, F: Fn(f64) -> f64
+{
+    type Y = T::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.inner.f(x)}
+    fn g(&self, x: X) -> Self::Y {self.inner.g(x)}
+    fn h(&self, x: X, s: f64) -> Self::Y {
+        debug_assert!((self.warp)(0.0) == 0.0 && (self.warp)(1.0) == 1.0,
+            "Expected warp to map 0 to 0 and 1 to 1");
+        self.inner.h(x, (self.warp)(s))
+    }
+}
+
+/// Plays a homotopy forward over the first half of the scalar range, then backward.
+///
+/// This is a closed homotopy where `h(_, 0) == h(_, 1)`, such that it loops seamlessly:
+/// both `f` and `g` are `f` of the wrapped homotopy, and the peak at `0.5` is its `g`.
+#[derive(Copy, Clone)]
+pub struct PingPong<T>(pub T);
+
+impl<X, T> Homotopy<X> for PingPong<T>
+    where T: Homotopy<X>
+{
+    type Y = T::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.0.f(x)}
+    fn g(&self, x: X) -> Self::Y {self.0.f(x)}
+    fn h(&self, x: X, s: f64) -> Self::Y {
+        if s <= 0.5 {self.0.h(x, 2.0 * s)} else {self.0.h(x, 2.0 * (1.0 - s))}
+    }
+}
@@ -0,0 +1,38 @@
+use super::*;
+
+/// Implemented by homotopies that can be converted into a GLSL function.
+pub trait ToGlsl {
+    /// Emits a GLSL function `vec2 name(float s)` computing the same points.
+    fn to_glsl(&self, name: &str) -> String;
+}
+
+/// Formats a 2D point as a GLSL `vec2` constructor.
+fn vec2(p: [f64; 2]) -> String {format!("vec2({:?}, {:?})", p[0], p[1])}
+
+impl ToGlsl for Lerp<[f64; 2]> {
+    fn to_glsl(&self, name: &str) -> String {
+        format!("vec2 {}(float s) {{\n    return mix({}, {}, s);\n}}\n",
+                name, vec2(self.0), vec2(self.1))
+    }
+}
+
+impl ToGlsl for CubicBezier<[f64; 2]> {
+    fn to_glsl(&self, name: &str) -> String {
+        format!("vec2 {}(float s) {{\n    \
+                 vec2 a = mix({}, {}, s);\n    \
+                 vec2 b = mix({}, {}, s);\n    \
+                 vec2 c = mix({}, {}, s);\n    \
+                 return mix(mix(a, b, s), mix(b, c, s), s);\n}}\n",
+                name, vec2(self.0), vec2(self.1), vec2(self.1), vec2(self.2),
+                vec2(self.2), vec2(self.3))
+    }
+}
+
+impl ToGlsl for Circle<f64> {
+    fn to_glsl(&self, name: &str) -> String {
+        format!("vec2 {}(float s) {{\n    \
+                 float a = {:?} * s;\n    \
+                 return {} + {:?} * vec2(cos(a), sin(a));\n}}\n",
+                name, 2.0 * std::f64::consts::PI, vec2(self.center), self.radius)
+    }
+}
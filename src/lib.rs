@@ -2,14 +2,48 @@
 
 #![deny(missing_docs)]
 
+#[cfg(feature = "image")]
+extern crate image;
+
 use std::ops::{Add, Sub, Mul};
 use std::marker::PhantomData;
+use std::convert::TryFrom;
 
 pub use sides::*;
 pub use compose::*;
+pub use ease::*;
+pub use player::*;
+pub use mesh::*;
+pub use vector::*;
+pub use analysis::*;
+pub use rotation::*;
+pub use svg::*;
+pub use path::*;
+pub use morph::*;
+pub use cache::*;
+pub use pattern::*;
+#[cfg(feature = "glsl")]
+pub use glsl::*;
+#[cfg(feature = "image")]
+pub use render::*;
 
 mod sides;
 mod compose;
+mod ease;
+mod player;
+mod mesh;
+mod vector;
+mod analysis;
+mod rotation;
+mod svg;
+mod path;
+mod morph;
+mod cache;
+mod pattern;
+#[cfg(feature = "glsl")]
+mod glsl;
+#[cfg(feature = "image")]
+mod render;
 
 /// A continuous map between two functions.
 pub trait Homotopy<X, Scalar=f64>: Sized {
@@ -26,8 +60,28 @@ pub trait Homotopy<X, Scalar=f64>: Sized {
     /// Call `h` with default value for `X`.
     ///
     /// This is often used by maps where `X` is a complex unit type, e.g. `((), ())`.
+    /// It works for any scalar type, such as `f64`, `[f64; 2]` and `[f64; 3]`.
     fn hu(&self, s: Scalar) -> Self::Y where X: Default {self.h(Default::default(), s)}
 
+    /// Call `h` with a scalar that is known to be within `[0, 1]`.
+    ///
+    /// Since `Unit` can only be constructed from values in range,
+    /// this can not receive an out-of-range scalar.
+    fn h_unit(&self, x: X, s: Unit) -> Self::Y where Unit: Into<Scalar> {self.h(x, s.into())}
+
+    /// Call `h` with the scalar given as a slice.
+    ///
+    /// This is useful when the scalar is built dynamically for a multi-dimensional homotopy.
+    /// Panics if the length of the slice does not match the dimension of the scalar.
+    fn h_slice(&self, x: X, s: &[f64]) -> Self::Y
+        where Scalar: for<'b> TryFrom<&'b [f64]>
+    {
+        match Scalar::try_from(s) {
+            Ok(s) => self.h(x, s),
+            Err(_) => panic!("Scalar has wrong dimension `{}`", s.len()),
+        }
+    }
+
     /// Gets the inverse.
     fn inverse<'a>(&'a self) -> Inverse<&'a Self> {Inverse(self)}
 
@@ -221,6 +275,20 @@ pub trait Homotopy<X, Scalar=f64>: Sized {
         PastFuture(self, s)
     }
 
+    /// Fixes a set of axes to given values, giving a reduced-dimension homotopy.
+    fn fix_axes<'a, S>(&'a self, fixed: &[(usize, f64)]) -> FixedAxes<&'a Self>
+        where FixedAxes<&'a Self>: Homotopy<X, S>
+    {
+        FixedAxes(self, fixed.to_vec())
+    }
+
+    /// Fixes a set of axes to given values, giving a reduced-dimension homotopy.
+    fn into_fix_axes<S>(self, fixed: &[(usize, f64)]) -> FixedAxes<Self>
+        where FixedAxes<Self>: Homotopy<X, S>
+    {
+        FixedAxes(self, fixed.to_vec())
+    }
+
     /// Gets a converter to and from vectors.
     fn as_vec<'a, S, VX>(&'a self) -> AsVec<&'a Self>
         where AsVec<&'a Self>: Homotopy<VX, S>
@@ -262,6 +330,48 @@ pub trait Homotopy<X, Scalar=f64>: Sized {
     {
         SMap::new(self, f)
     }
+
+    /// Reparametrizes the scalar by a warp function that maps `0` to `0` and `1` to `1`.
+    fn time_warp<'a, F: Fn(f64) -> f64>(&'a self, warp: F) -> TimeWarp<&'a Self, F>
+        where TimeWarp<&'a Self, F>: Homotopy<X, Scalar>
+    {
+        TimeWarp {inner: self, warp}
+    }
+
+    /// Reparametrizes the scalar by a warp function that maps `0` to `0` and `1` to `1`.
+    fn into_time_warp<F: Fn(f64) -> f64>(self, warp: F) -> TimeWarp<Self, F>
+        where TimeWarp<Self, F>: Homotopy<X, Scalar>
+    {
+        TimeWarp {inner: self, warp}
+    }
+
+    /// Plays forward then backward, giving a closed homotopy that returns to the start.
+    fn ping_pong<'a>(&'a self) -> PingPong<&'a Self>
+        where PingPong<&'a Self>: Homotopy<X, Scalar>
+    {
+        PingPong(self)
+    }
+
+    /// Plays forward then backward, giving a closed homotopy that returns to the start.
+    fn into_ping_pong(self) -> PingPong<Self>
+        where PingPong<Self>: Homotopy<X, Scalar>
+    {
+        PingPong(self)
+    }
+
+    /// Blends with another homotopy by a fixed weight, e.g. to cross-fade two animations.
+    fn blend<'a, H2>(&'a self, other: H2, weight: f64) -> Blend<&'a Self, H2>
+        where Blend<&'a Self, H2>: Homotopy<X, Scalar>
+    {
+        Blend {a: self, b: other, weight}
+    }
+
+    /// Blends with another homotopy by a fixed weight, e.g. to cross-fade two animations.
+    fn into_blend<H2>(self, other: H2, weight: f64) -> Blend<Self, H2>
+        where Blend<Self, H2>: Homotopy<X, Scalar>
+    {
+        Blend {a: self, b: other, weight}
+    }
 }
 
 impl<'a, X, T, S> Homotopy<X, S> for &'a T
@@ -410,6 +520,149 @@ pub fn checku4<H, X>(h: &H) -> bool
     checku3(&h.future())
 }
 
+/// Checks that the N-dimensional homotopy constraints hold for some input `x`.
+///
+/// This generalizes `check2`, `check3` and `check4` to any scalar of dimension `dim`
+/// that can be constructed from a slice, such as `[f64; 5]`.
+/// The sides checked recursively by those functions share `f` when fixed at `0.0`
+/// and `g` when fixed at `1.0`, while their other endpoints are defined by `h`,
+/// so the constraints reduce to checking the corners `[0.0; dim]` and `[1.0; dim]`.
+/// Panics if `dim` does not match the dimension of the scalar.
+#[must_use]
+pub fn check_nd<H, X, S>(h: &H, x: X, dim: usize) -> bool
+    where H: Homotopy<X, S>,
+          H::Y: PartialEq,
+          X: Clone,
+          S: for<'b> TryFrom<&'b [f64]>
+{
+    h.h_slice(x.clone(), &vec![0.0; dim]) == h.f(x.clone()) &&
+    h.h_slice(x.clone(), &vec![1.0; dim]) == h.g(x)
+}
+
+/// Implemented by outputs that can be compared within a tolerance.
+pub trait ApproxEq {
+    /// Returns `true` if every component differs by at most `epsilon`.
+    fn approx_eq(&self, other: &Self, epsilon: f64) -> bool;
+}
+
+impl ApproxEq for f64 {
+    fn approx_eq(&self, other: &f64, epsilon: f64) -> bool {(self - other).abs() <= epsilon}
+}
+
+impl ApproxEq for [f64; 2] {
+    fn approx_eq(&self, other: &[f64; 2], epsilon: f64) -> bool {
+        self.iter().zip(other.iter()).all(|(a, b)| a.approx_eq(b, epsilon))
+    }
+}
+
+impl ApproxEq for [f64; 3] {
+    fn approx_eq(&self, other: &[f64; 3], epsilon: f64) -> bool {
+        self.iter().zip(other.iter()).all(|(a, b)| a.approx_eq(b, epsilon))
+    }
+}
+
+impl ApproxEq for [f64; 4] {
+    fn approx_eq(&self, other: &[f64; 4], epsilon: f64) -> bool {
+        self.iter().zip(other.iter()).all(|(a, b)| a.approx_eq(b, epsilon))
+    }
+}
+
+/// Checks that the homotopy constraints hold for some input `x`, within a tolerance.
+///
+/// This is useful when `h` is computed in a way that differs from `f` and `g` by rounding.
+#[must_use]
+pub fn check_approx<H, X>(h: &H, x: X, epsilon: f64) -> bool
+    where H: Homotopy<X>,
+          H::Y: ApproxEq,
+          X: Clone
+{
+    h.h(x.clone(), 0.0).approx_eq(&h.f(x.clone()), epsilon) &&
+    h.h(x.clone(), 1.0).approx_eq(&h.g(x), epsilon)
+}
+
+/// Checks that the 2D homotopy constraints hold for some input `x`, within a tolerance.
+#[must_use]
+pub fn check2_approx<H, X>(h: &H, x: X, epsilon: f64) -> bool
+    where H: Homotopy<X, [f64; 2]>,
+          H::Y: ApproxEq,
+          X: Clone,
+{
+    let a = h.f(x.clone());
+    let b = h.g(x.clone());
+    h.h(x.clone(), [0.0, 0.0]).approx_eq(&a, epsilon) &&
+    h.h(x.clone(), [1.0, 1.0]).approx_eq(&b, epsilon) &&
+    check_approx(&h.left(), x.clone(), epsilon) &&
+    check_approx(&h.right(), x.clone(), epsilon) &&
+    check_approx(&h.top(), x.clone(), epsilon) &&
+    check_approx(&h.bottom(), x.clone(), epsilon)
+}
+
+/// Checks that the 3D homotopy constraints hold for some input `x`, within a tolerance.
+#[must_use]
+pub fn check3_approx<H, X>(h: &H, x: X, epsilon: f64) -> bool
+    where H: Homotopy<X, [f64; 3]>,
+          H::Y: ApproxEq,
+          X: Clone,
+{
+    let a = h.f(x.clone());
+    let b = h.g(x.clone());
+    h.h(x.clone(), [0.0, 0.0, 0.0]).approx_eq(&a, epsilon) &&
+    h.h(x.clone(), [1.0, 1.0, 1.0]).approx_eq(&b, epsilon) &&
+    check2_approx(&h.left(), x.clone(), epsilon) &&
+    check2_approx(&h.right(), x.clone(), epsilon) &&
+    check2_approx(&h.top(), x.clone(), epsilon) &&
+    check2_approx(&h.bottom(), x.clone(), epsilon) &&
+    check2_approx(&h.front(), x.clone(), epsilon) &&
+    check2_approx(&h.back(), x.clone(), epsilon)
+}
+
+/// Checks that a homotopy has no jumps, by sampling `h` at `n + 1` evenly spaced scalars.
+///
+/// The `check` functions only verify the endpoints, so a discontinuous `h` still passes them.
+/// This returns the scalar of the first sample that differs from the previous sample
+/// by more than `threshold` in any component.
+/// `Dirac` and `DiracFrom` are expected to fail this check, since they jump right after `0.0`.
+/// A continuous homotopy may also fail when `n` is too small for the threshold.
+pub fn check_continuity<H, X>(h: &H, x: X, n: u32, threshold: f64) -> Result<(), f64>
+    where H: Homotopy<X>,
+          H::Y: ApproxEq,
+          X: Clone
+{
+    let mut prev = h.h(x.clone(), 0.0);
+    for i in 1..n + 1 {
+        let s = i as f64 / n as f64;
+        let y = h.h(x.clone(), s);
+        if !y.approx_eq(&prev, threshold) {return Err(s)};
+        prev = y;
+    }
+    Ok(())
+}
+
+/// Returns the default input for a homotopy, e.g. to pass to `check2`.
+///
+/// Supported input shapes are those implementing `Default`, which includes `()`,
+/// tuples of units such as `((), ())` used by `Square` and `((), (), ())` used by `Cube`,
+/// nested tuples such as `(((), ()), ())`, and arrays of units such as `[(); 3]` used by `AsVec`.
+pub fn unit_input<H, X, S>(_: &H) -> X
+    where H: Homotopy<X, S>, X: Default
+{
+    Default::default()
+}
+
+/// Checks that the diagonal of a square equals evaluating both homotopies at the same scalar.
+///
+/// Samples `n + 1` evenly spaced scalars.
+#[must_use]
+pub fn check_diagonal_equiv<A, B>(a: &A, b: &B, n: u32) -> bool
+    where A: Homotopy<()>, B: Homotopy<()>, A::Y: PartialEq, B::Y: PartialEq
+{
+    let diag = Square::new(a, b).into_diagonal();
+    (0..n + 1).all(|i| {
+        let s = i as f64 / n as f64;
+        diag.h(((), ()), s) == (a.h((), s), b.h((), s))
+    })
+}
+
 /// Identity homotopy.
 ///
 /// `f`, `g` and `h` uses the identity function, so this is a homotopy.
@@ -424,6 +677,23 @@ impl<X, S> Homotopy<X, S> for Id {
     fn h(&self, x: X, _: S) -> X {x}
 }
 
+/// Constant homotopy.
+///
+/// `f`, `g` and `h` return the same value, ignoring the input and the scalar.
+/// This is useful as a building block, e.g. to hold one part of a blend fixed.
+#[derive(Copy, Clone)]
+pub struct Constant<Y>(pub Y);
+
+impl<X, S, Y> Homotopy<X, S> for Constant<Y>
+    where Y: Clone
+{
+    type Y = Y;
+
+    fn f(&self, _: X) -> Y {self.0.clone()}
+    fn g(&self, _: X) -> Y {self.0.clone()}
+    fn h(&self, _: X, _: S) -> Y {self.0.clone()}
+}
+
 /// The Dirac function.
 #[derive(Copy, Clone)]
 pub struct Dirac;
@@ -440,6 +710,7 @@ impl Homotopy<()> for Dirac {
 ///
 /// Define `h` to be `f` at 0.0 and `g` elsewhere.
 /// Since `h` is `g` at 1.0, this is a homotopy.
+/// It is not continuous, so it fails `check_continuity`.
 #[derive(Copy, Clone)]
 pub struct DiracFrom<X, Y, F, G>
     where F: Fn(X) -> Y, G: Fn(X) -> Y
@@ -479,6 +750,27 @@ impl<X, Y, F, G> Homotopy<X> for DiracFrom<X, Y, F, G>
     }
 }
 
+/// Frame index of a sprite sequence.
+///
+/// Divides the scalar into `count` equally long frames,
+/// going from frame `0` to frame `count - 1`.
+/// A count of zero is treated like a single frame, always giving frame `0`.
+#[derive(Copy, Clone)]
+pub struct FrameSeq {
+    /// The number of frames.
+    pub count: u32,
+}
+
+impl Homotopy<()> for FrameSeq {
+    type Y = u32;
+
+    fn f(&self, _: ()) -> u32 {0}
+    fn g(&self, _: ()) -> u32 {self.count.saturating_sub(1)}
+    fn h(&self, _: (), s: f64) -> u32 {
+        ((s * self.count as f64).floor().max(0.0) as u32).min(self.count.saturating_sub(1))
+    }
+}
+
 /// Linear interpolation homotopy.
 ///
 /// `f` and `g` are functions mapping `()` to a value.
@@ -496,6 +788,75 @@ impl<Y> Homotopy<()> for Lerp<Y>
     fn h(&self, _: (), s: f64) -> Y {self.0.clone() * (1.0 - s) + self.1.clone() * s}
 }
 
+/// Linear interpolation homotopy for any type implementing `Tween`.
+///
+/// Works like `Lerp`, but without requiring `Mul<f64>` and `Add`,
+/// so it also works for arrays and custom types.
+#[derive(Copy, Clone)]
+pub struct AutoLerp<T>(pub T, pub T);
+
+impl<T> Homotopy<()> for AutoLerp<T>
+    where T: Tween + Clone
+{
+    type Y = T;
+
+    fn f(&self, _: ()) -> T {self.0.clone()}
+    fn g(&self, _: ()) -> T {self.1.clone()}
+    fn h(&self, _: (), s: f64) -> T {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.f(())} else if s == 1.0 {return self.g(())};
+        self.0.tween(&self.1, s)
+    }
+}
+
+/// Smoothstep interpolation homotopy.
+///
+/// Works like `Lerp`, but remaps the scalar with the Hermite polynomial `3s² - 2s³`,
+/// which starts and ends with zero velocity.
+#[derive(Copy, Clone)]
+pub struct Smoothstep<X>(pub X, pub X);
+
+impl<X> Smoothstep<X> {
+    /// Creates a smoothstep between the endpoints of a linear interpolation.
+    pub fn from_lerp(lerp: Lerp<X>) -> Smoothstep<X> {Smoothstep(lerp.0, lerp.1)}
+}
+
+impl<Y> Homotopy<()> for Smoothstep<Y>
+    where Y: Mul<f64, Output = Y> + Add<Output = Y> + Clone
+{
+    type Y = Y;
+
+    fn f(&self, _: ()) -> Y {self.0.clone()}
+    fn g(&self, _: ()) -> Y {self.1.clone()}
+    fn h(&self, _: (), s: f64) -> Y {
+        Lerp(self.0.clone(), self.1.clone()).h((), Easing::SmoothStep.apply(s))
+    }
+}
+
+/// Smootherstep interpolation homotopy.
+///
+/// Works like `Lerp`, but remaps the scalar with the quintic `6s⁵ - 15s⁴ + 10s³`,
+/// which starts and ends with zero velocity and zero acceleration.
+#[derive(Copy, Clone)]
+pub struct Smootherstep<X>(pub X, pub X);
+
+impl<X> Smootherstep<X> {
+    /// Creates a smootherstep between the endpoints of a linear interpolation.
+    pub fn from_lerp(lerp: Lerp<X>) -> Smootherstep<X> {Smootherstep(lerp.0, lerp.1)}
+}
+
+impl<Y> Homotopy<()> for Smootherstep<Y>
+    where Y: Mul<f64, Output = Y> + Add<Output = Y> + Clone
+{
+    type Y = Y;
+
+    fn f(&self, _: ()) -> Y {self.0.clone()}
+    fn g(&self, _: ()) -> Y {self.1.clone()}
+    fn h(&self, _: (), s: f64) -> Y {
+        Lerp(self.0.clone(), self.1.clone()).h((), Easing::SmootherStep.apply(s))
+    }
+}
+
 /// Quadratic Bezier homotopy.
 ///
 /// Maps from point A to C using a point B as control point.
@@ -509,6 +870,35 @@ impl<X> QuadraticBezier<X> {
     {
         QuadraticBezier(a.clone(), a * 0.5 + b.clone() * 0.5, b)
     }
+
+    /// Returns the centroid of the control points.
+    pub fn control_centroid(&self) -> X
+        where X: Mul<f64, Output = X> + Add<Output = X> + Clone
+    {
+        (self.0.clone() + self.1.clone() + self.2.clone()) * (1.0 / 3.0)
+    }
+
+    /// Returns the centroid of the intermediate de Casteljau points at `s`.
+    ///
+    /// These are the two points interpolated between the control points
+    /// and the point on the curve interpolated between them.
+    pub fn centroid_at(&self, s: f64) -> X
+        where X: Mul<f64, Output = X> + Add<Output = X> + Clone
+    {
+        let a = Lerp(self.0.clone(), self.1.clone()).h((), s);
+        let b = Lerp(self.1.clone(), self.2.clone()).h((), s);
+        let p = Lerp(a.clone(), b.clone()).h((), s);
+        (a + b + p) * (1.0 / 3.0)
+    }
+
+    /// Returns the derivative curve, scaled by the degree.
+    ///
+    /// Sampling the hodograph at `s` gives the exact tangent of the curve at `s`.
+    pub fn hodograph(&self) -> Lerp<X>
+        where X: Mul<f64, Output = X> + Sub<Output = X> + Clone
+    {
+        Lerp((self.1.clone() - self.0.clone()) * 2.0, (self.2.clone() - self.1.clone()) * 2.0)
+    }
 }
 
 impl<X> From<Lerp<X>> for QuadraticBezier<X>
@@ -542,14 +932,84 @@ pub struct CubicBezier<X>(pub X, pub X, pub X, pub X);
 impl<X> CubicBezier<X> {
     /// Creates a cubic bezier that is identical to quadratic bezier.
     pub fn from_quadratic(a: X, b: X, c: X) -> CubicBezier<X>
-        where X: Clone
+        where X: Mul<f64, Output = X> + Add<Output = X> + Clone
+    {
+        let b1 = a.clone() * (1.0 / 3.0) + b.clone() * (2.0 / 3.0);
+        let b2 = b * (2.0 / 3.0) + c.clone() * (1.0 / 3.0);
+        CubicBezier(a, b1, b2, c)
+    }
+
+    /// Returns the centroid of the control points.
+    pub fn control_centroid(&self) -> X
+        where X: Mul<f64, Output = X> + Add<Output = X> + Clone
+    {
+        (self.0.clone() + self.1.clone() + self.2.clone() + self.3.clone()) * 0.25
+    }
+
+    /// Returns the centroid of the intermediate de Casteljau points at `s`.
+    ///
+    /// These are the three points interpolated between the control points,
+    /// the two points interpolated between those, and the point on the curve.
+    pub fn centroid_at(&self, s: f64) -> X
+        where X: Mul<f64, Output = X> + Add<Output = X> + Clone
+    {
+        let a = Lerp(self.0.clone(), self.1.clone()).h((), s);
+        let b = Lerp(self.1.clone(), self.2.clone()).h((), s);
+        let c = Lerp(self.2.clone(), self.3.clone()).h((), s);
+        let d = Lerp(a.clone(), b.clone()).h((), s);
+        let e = Lerp(b.clone(), c.clone()).h((), s);
+        let p = Lerp(d.clone(), e.clone()).h((), s);
+        (a + b + c + d + e + p) * (1.0 / 6.0)
+    }
+
+    /// Returns the derivative curve, scaled by the degree.
+    ///
+    /// Sampling the hodograph at `s` gives the exact tangent of the curve at `s`.
+    pub fn hodograph(&self) -> QuadraticBezier<X>
+        where X: Mul<f64, Output = X> + Sub<Output = X> + Clone
     {
-        CubicBezier(a, b.clone(), b, c)
+        QuadraticBezier(
+            (self.1.clone() - self.0.clone()) * 3.0,
+            (self.2.clone() - self.1.clone()) * 3.0,
+            (self.3.clone() - self.2.clone()) * 3.0,
+        )
+    }
+}
+
+impl CubicBezier<[f64; 2]> {
+    /// Returns the parameters in `[0, 1]` where the curve changes its direction of bending.
+    ///
+    /// These are the roots of the cross product of the first and second derivatives,
+    /// which is a quadratic in the parameter. The parameters are returned in increasing order.
+    pub fn inflection_points(&self) -> Vec<f64> {
+        let cross = |u: [f64; 2], v: [f64; 2]| u[0] * v[1] - u[1] * v[0];
+        let [p0, p1, p2, p3] = [self.0, self.1, self.2, self.3];
+        let a = p1.sub_vec(p0);
+        let b = p2.sub_vec(p1.scale(2.0)).add_vec(p0);
+        let c = p3.sub_vec(p2.scale(3.0)).add_vec(p1.scale(3.0)).sub_vec(p0);
+        // Solve `qa * s^2 + qb * s + qc = 0`.
+        let qa = cross(b, c);
+        let qb = cross(a, c);
+        let qc = cross(a, b);
+        let mut roots = vec![];
+        if qa.abs() < 1e-12 {
+            if qb.abs() >= 1e-12 {roots.push(-qc / qb)};
+        } else {
+            let disc = qb * qb - 4.0 * qa * qc;
+            if disc > 0.0 {
+                let sq = disc.sqrt();
+                roots.push((-qb - sq) / (2.0 * qa));
+                roots.push((-qb + sq) / (2.0 * qa));
+            }
+        }
+        roots.retain(|s| (0.0..=1.0).contains(s));
+        roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        roots
     }
 }
 
 impl<X> From<QuadraticBezier<X>> for CubicBezier<X>
-    where X: Clone
+    where X: Mul<f64, Output = X> + Add<Output = X> + Clone
 {
     fn from(QuadraticBezier(a, b, c): QuadraticBezier<X>) -> CubicBezier<X> {
         CubicBezier::from_quadratic(a, b, c)
@@ -565,8 +1025,9 @@ impl<Y> Homotopy<()> for CubicBezier<Y>
     fn g(&self, _: ()) -> Y {self.3.clone()}
     fn h(&self, _: (), s: f64) -> Y {
         let a = Lerp(self.0.clone(), self.1.clone()).h((), s);
-        let b = Lerp(self.2.clone(), self.3.clone()).h((), s);
-        Lerp(a, b).h((), s)
+        let b = Lerp(self.1.clone(), self.2.clone()).h((), s);
+        let c = Lerp(self.2.clone(), self.3.clone()).h((), s);
+        QuadraticBezier(a, b, c).h((), s)
     }
 }
 
@@ -649,6 +1110,9 @@ pub struct Cube4<X1, X2, X3, X4, H1, H2, H3, H4>
     _x4: PhantomData<X4>,
 }
 
+/// The 4-cube of four homotopy maps, also known as a tesseract.
+pub type Tesseract<X1, X2, X3, X4, H1, H2, H3, H4> = Cube4<X1, X2, X3, X4, H1, H2, H3, H4>;
+
 impl<X1, X2, X3, X4, H1, H2, H3, H4> Cube4<X1, X2, X3, X4, H1, H2, H3, H4>
     where H1: Homotopy<X1>, H2: Homotopy<X2>, H3: Homotopy<X3>, H4: Homotopy<X4>
 {
@@ -756,6 +1220,11 @@ impl<X, Y, S, T> Homotopy<[X; 4], S> for AsVec<T>
 }
 
 /// Generates points on a circle.
+///
+/// The scalar `s` maps to the point at angle `2πs`, counter-clockwise from the positive x-axis.
+/// This is a closed homotopy: `f` and `g` are the same point `[center[0] + radius, center[1]]`,
+/// so the start and end of the map coincide and the curve forms a loop.
+/// The quarter points are handled specially to get exact values.
 #[derive(Copy, Clone)]
 pub struct Circle<T> {
     /// Center of circle.
@@ -793,6 +1262,66 @@ impl<T> Homotopy<()> for Circle<T>
     }
 }
 
+impl<T> Circle<T> {
+    /// Traverses the circle with non-uniform angular speed given by an easing.
+    pub fn eased(self, easing: Easing) -> Eased<Circle<T>> {Eased(self, easing)}
+}
+
+impl Circle<f64> {
+    /// Returns the circle as four rational quadratic Bezier arcs of 90° each.
+    ///
+    /// The arcs start at the same point as the circle and run in the same direction,
+    /// with weight `√2/2` on the middle control point, which makes them exact circular arcs.
+    /// Unlike the circle, each arc is not traversed with uniform angular speed.
+    pub fn as_rational_beziers(&self) -> Vec<RationalBezier<[f64; 2]>> {
+        let [cx, cy] = self.center;
+        let r = self.radius;
+        let dirs = [[1.0, 0.0], [0.0, 1.0], [-1.0, 0.0], [0.0, -1.0], [1.0, 0.0]];
+        let w = std::f64::consts::FRAC_1_SQRT_2;
+        dirs.windows(2).map(|d| {
+            let (a, b) = (d[0], d[1]);
+            RationalBezier::new(vec![
+                [cx + r * a[0], cy + r * a[1]],
+                [cx + r * (a[0] + b[0]), cy + r * (a[1] + b[1])],
+                [cx + r * b[0], cy + r * b[1]],
+            ], vec![1.0, w, 1.0])
+        }).collect()
+    }
+}
+
+/// Blends the outputs of two homotopies by a fixed weight.
+///
+/// This is `a.h(x, s) * (1 - weight) + b.h(x, s) * weight`,
+/// where `f` and `g` are the blended endpoints.
+/// The result is linear in the weight, giving `a` at `0.0` and `b` at `1.0`.
+#[derive(Copy, Clone)]
+pub struct Blend<H1, H2> {
+    /// The homotopy at weight `0.0`.
+    pub a: H1,
+    /// The homotopy at weight `1.0`.
+    pub b: H2,
+    /// The weight of `b`.
+    pub weight: f64,
+}
+
+impl<X, S, H1, H2> Homotopy<X, S> for Blend<H1, H2>
+    where H1: Homotopy<X, S>, H2: Homotopy<X, S, Y = H1::Y>,
+          H1::Y: Mul<f64, Output = H1::Y> + Add<Output = H1::Y>,
+          X: Clone, S: Clone
+{
+    type Y = H1::Y;
+
+    fn f(&self, x: X) -> Self::Y {
+        self.a.f(x.clone()) * (1.0 - self.weight) + self.b.f(x) * self.weight
+    }
+    fn g(&self, x: X) -> Self::Y {
+        self.a.g(x.clone()) * (1.0 - self.weight) + self.b.g(x) * self.weight
+    }
+    fn h(&self, x: X, s: S) -> Self::Y {
+        self.a.h(x.clone(), s.clone()) * (1.0 - self.weight) + self.b.h(x, s) * self.weight
+    }
+}
+
 /// Maps output of homotopy map from one form into another.
 #[derive(Clone, Copy)]
 pub struct Map<H, F, Y1, Y2>
@@ -963,57 +1492,251 @@ impl Homotopy<[f64; 4]> for Translate<[f64; 4]> {
     }
 }
 
-/// Create a sweep from two circles.
+/// Smooths a homotopy by averaging its output over a window of the scalar.
 ///
-/// This is constructed by taking the diagonal of the square product of two circles.
-/// It can be thought of as making the circles rotate together, controlled by a single parameter.
-///
-/// Then, a SMap adds a new dimension that interpolates along the sweep,
-/// making it possible to control both the rotation and position between the two circles.
-pub fn sweep(a: Circle<f64>, b: Circle<f64>) -> impl Homotopy<((), ()), [f64; 2], Y = [f64; 2]> {
-    Square::new(a, b).into_diagonal().into_smap(|(a, b), s| [
-            a[0] + (b[0] - a[0]) * s,
-            a[1] + (b[1] - a[1]) * s,
-        ])
-}
+/// The fields are the wrapped homotopy, the half-width of the window and the number of samples.
+/// The window is shrunk near the ends so it stays within `[0, 1]` and is centered at `s`,
+/// which keeps `f` and `g` pinned to the endpoints of the wrapped homotopy.
+#[derive(Copy, Clone)]
+pub struct Smooth<T>(pub T, pub f64, pub u32);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl<X, T> Homotopy<X> for Smooth<T>
+    where T: Homotopy<X>, T::Y: Add<Output = T::Y> + Mul<f64, Output = T::Y> + Default,
+          X: Clone
+{
+    type Y = T::Y;
 
-    #[test]
-    fn check_id() {
-        assert!(check(&Id, 0.0 as f64));
-        assert!(check(&Id, 1.0 as f64));
-        assert!(check(&Id, true));
-        assert!(check(&Id, false));
+    fn f(&self, x: X) -> Self::Y {self.0.f(x)}
+    fn g(&self, x: X) -> Self::Y {self.0.g(x)}
+    fn h(&self, x: X, s: f64) -> Self::Y {
+        let w = self.1.min(s).min(1.0 - s);
+        let n = self.2;
+        if w <= 0.0 || n < 2 {return self.0.h(x, s)};
+        let mut sum: T::Y = Default::default();
+        for i in 0..n {
+            let t = s - w + 2.0 * w * i as f64 / (n - 1) as f64;
+            sum = sum + self.0.h(x.clone(), t);
+        }
+        sum * (1.0 / n as f64)
     }
+}
 
-    #[test]
-    fn check_dirac() {
-        assert!(checku(&Dirac));
-    }
+/// Pairs a 2D position with an opacity, sharing the same scalar.
+///
+/// This is used for effects that fade and move at the same time,
+/// where the position and the opacity can follow their own curves.
+#[derive(Copy, Clone)]
+pub struct WithAlpha<P, A>(pub P, pub A);
 
-    #[test]
-    fn check_dirac_from() {
-        let ft = DiracFrom::new(|()| 1.0, |()| 0.0);
-        assert!(checku(&ft));
+impl<P, A> WithAlpha<P, A>
+    where P: Homotopy<(), Y = [f64; 2]>, A: Homotopy<(), Y = f64>
+{
+    /// Returns the color `[r, g, b]` combined with the opacity at `s`.
+    pub fn to_rgba(&self, color: [f64; 3], s: f64) -> [f64; 4] {
+        [color[0], color[1], color[2], self.1.hu(s)]
     }
+}
 
-    #[test]
-    fn check_lerp() {
-        let lerp = Lerp(1.2, 1.3);
-        assert!(checku(&lerp));
-    }
+impl<X, P, A> Homotopy<X> for WithAlpha<P, A>
+    where P: Homotopy<X, Y = [f64; 2]>, A: Homotopy<X, Y = f64>, X: Clone
+{
+    type Y = ([f64; 2], f64);
 
-    #[test]
-    fn check_quadratic_bezier() {
-        let qb = QuadraticBezier(0.3, 0.7, 0.9);
-        assert!(checku(&qb));
-    }
+    fn f(&self, x: X) -> Self::Y {(self.0.f(x.clone()), self.1.f(x))}
+    fn g(&self, x: X) -> Self::Y {(self.0.g(x.clone()), self.1.g(x))}
+    fn h(&self, x: X, s: f64) -> Self::Y {(self.0.h(x.clone(), s), self.1.h(x, s))}
+}
 
-    #[test]
-    fn check_cubic_bezier() {
+/// Clamps the output of a 2D homotopy into an axis-aligned box.
+///
+/// The fields are the wrapped homotopy, the minimum corner and the maximum corner.
+/// Each output component is clamped into `[min, max]` of its axis, including `f` and `g`.
+///
+/// Clamping preserves the homotopy constraints, but the output stops moving at the boundary,
+/// so the map is not differentiable where it enters or leaves the box.
+#[derive(Copy, Clone)]
+pub struct ClampBox<T>(pub T, pub [f64; 2], pub [f64; 2]);
+
+impl<T> ClampBox<T> {
+    fn clamp(&self, p: [f64; 2]) -> [f64; 2] {
+        [
+            p[0].max(self.1[0]).min(self.2[0]),
+            p[1].max(self.1[1]).min(self.2[1]),
+        ]
+    }
+}
+
+impl<X, S, T> Homotopy<X, S> for ClampBox<T>
+    where T: Homotopy<X, S, Y = [f64; 2]>
+{
+    type Y = [f64; 2];
+
+    fn f(&self, x: X) -> Self::Y {self.clamp(self.0.f(x))}
+    fn g(&self, x: X) -> Self::Y {self.clamp(self.0.g(x))}
+    fn h(&self, x: X, s: S) -> Self::Y {self.clamp(self.0.h(x, s))}
+}
+
+/// Gives access to the components of a scalar, used to validate scalars at runtime.
+pub trait ScalarComponents {
+    /// Returns the components of the scalar.
+    fn components(&self) -> &[f64];
+}
+
+impl ScalarComponents for f64 {
+    fn components(&self) -> &[f64] {std::slice::from_ref(self)}
+}
+
+impl ScalarComponents for [f64; 2] {
+    fn components(&self) -> &[f64] {self}
+}
+
+impl ScalarComponents for [f64; 3] {
+    fn components(&self) -> &[f64] {self}
+}
+
+impl ScalarComponents for [f64; 4] {
+    fn components(&self) -> &[f64] {self}
+}
+
+/// A scalar component outside `[0, 1]`, reported by `Bounded::try_h`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OutOfBounds {
+    /// The index of the first component outside `[0, 1]`.
+    pub index: usize,
+    /// The value of the component.
+    pub value: f64,
+}
+
+/// Validates that every component of the scalar is within `[0, 1]`.
+///
+/// Unlike clamping, which silently fixes out-of-range scalars,
+/// this surfaces them as errors through `try_h`, or panics when used as a homotopy.
+#[derive(Copy, Clone)]
+pub struct Bounded<T>(pub T);
+
+impl<T> Bounded<T> {
+    /// Evaluates the wrapped homotopy, or returns an error if the scalar is out of bounds.
+    pub fn try_h<X, S>(&self, x: X, s: S) -> Result<T::Y, OutOfBounds>
+        where T: Homotopy<X, S>, S: ScalarComponents
+    {
+        if let Some((index, &value)) = s.components().iter().enumerate()
+            .find(|&(_, v)| !(0.0..=1.0).contains(v))
+        {
+            return Err(OutOfBounds {index, value});
+        }
+        Ok(self.0.h(x, s))
+    }
+}
+
+impl<X, S, T> Homotopy<X, S> for Bounded<T>
+    where T: Homotopy<X, S>, S: ScalarComponents
+{
+    type Y = T::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.0.f(x)}
+    fn g(&self, x: X) -> Self::Y {self.0.g(x)}
+    fn h(&self, x: X, s: S) -> Self::Y {
+        match self.try_h(x, s) {
+            Ok(y) => y,
+            Err(err) => panic!("Scalar component {} out of bounds: {}", err.index, err.value),
+        }
+    }
+}
+
+/// A scalar that is guaranteed to be within `[0, 1]`.
+///
+/// This makes the range of the scalar an invariant of the type instead of a runtime check.
+/// Use with `Homotopy::h_unit`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Unit(f64);
+
+impl Unit {
+    /// Creates a new unit scalar, or returns `None` if the value is outside `[0, 1]`.
+    pub fn new(value: f64) -> Option<Unit> {
+        if (0.0..=1.0).contains(&value) {Some(Unit(value))} else {None}
+    }
+
+    /// Returns the value of the scalar.
+    pub fn get(self) -> f64 {self.0}
+}
+
+impl From<Unit> for f64 {
+    fn from(s: Unit) -> f64 {s.0}
+}
+
+/// Create a sweep from two circles.
+///
+/// This is constructed by taking the diagonal of the square product of two circles.
+/// It can be thought of as making the circles rotate together, controlled by a single parameter.
+///
+/// Then, a SMap adds a new dimension that interpolates along the sweep,
+/// making it possible to control both the rotation and position between the two circles.
+///
+/// This is equivalent to `Square::new(a, b).into_diagonal().into_smap(..)`,
+/// where the first scalar component is the angle along both circles
+/// and the second component moves from the first circle to the second.
+/// The result can be further transformed with `map`, or reduced to 1D with `left_right`.
+///
+/// ```
+/// use homotopy::*;
+///
+/// let inner = Circle {center: [0.0, 0.0], radius: 1.0};
+/// let outer = Circle {center: [0.0, 0.0], radius: 2.0};
+/// let c = sweep(inner, outer);
+/// assert!(checku2(&c));
+/// assert_eq!(c.hu([0.0, 0.0]), [1.0, 0.0]);
+/// assert_eq!(c.hu([0.25, 0.5]), [0.0, 1.5]);
+/// assert_eq!(c.hu([0.5, 1.0]), [-2.0, 0.0]);
+///
+/// // Fixing the angle gives a line from the inner to the outer circle.
+/// let ray = c.left_right(0.25);
+/// assert_eq!(ray.hu(0.0), [0.0, 1.0]);
+/// assert_eq!(ray.hu(1.0), [0.0, 2.0]);
+/// ```
+pub fn sweep(a: Circle<f64>, b: Circle<f64>) -> impl Homotopy<((), ()), [f64; 2], Y = [f64; 2]> {
+    Square::new(a, b).into_diagonal().into_smap(|(a, b), s| [
+            a[0] + (b[0] - a[0]) * s,
+            a[1] + (b[1] - a[1]) * s,
+        ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_id() {
+        assert!(check(&Id, 0.0 as f64));
+        assert!(check(&Id, 1.0 as f64));
+        assert!(check(&Id, true));
+        assert!(check(&Id, false));
+    }
+
+    #[test]
+    fn check_dirac() {
+        assert!(checku(&Dirac));
+    }
+
+    #[test]
+    fn check_dirac_from() {
+        let ft = DiracFrom::new(|()| 1.0, |()| 0.0);
+        assert!(checku(&ft));
+    }
+
+    #[test]
+    fn check_lerp() {
+        let lerp = Lerp(1.2, 1.3);
+        assert!(checku(&lerp));
+    }
+
+    #[test]
+    fn check_quadratic_bezier() {
+        let qb = QuadraticBezier(0.3, 0.7, 0.9);
+        assert!(checku(&qb));
+    }
+
+    #[test]
+    fn check_cubic_bezier() {
         let cb = CubicBezier(0.3, 0.7, 0.8, 0.9);
         assert!(checku(&cb));
     }
@@ -1030,13 +1753,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn check_cubic_bezier_equals_bernstein() {
+        let (a, b, c, d) = (0.3, 1.7, -0.8, 0.9);
+        let cb = CubicBezier(a, b, c, d);
+        let mut s = 0.0;
+        loop {
+            let t = 1.0 - s;
+            let expected = t * t * t * a + 3.0 * t * t * s * b +
+                           3.0 * t * s * s * c + s * s * s * d;
+            assert!((cb.hu(s) - expected).abs() < 0.000001);
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+    }
+
     #[test]
     fn check_reduced_cubic_bezier_equals_quadratic_bezier() {
         let cb = CubicBezier::from_quadratic(0.0, 0.3, 0.9);
         let qb = QuadraticBezier(0.0, 0.3, 0.9);
         let mut s = 0.0;
         loop {
-            assert_eq!(cb.hu(s), qb.hu(s));
+            assert!((cb.hu(s) - qb.hu(s)).abs() < 0.000001);
             s += 0.1;
             if s > 1.0 {break}
         }
@@ -1209,4 +1947,1802 @@ mod tests {
         assert_eq!(d.hu([1.0, 1.0, 1.0]), 22.0);
         assert!(checku3(&d));
     }
+
+    #[test]
+    fn check_axis_ease() {
+        let a = Circle {center: [0.0, 0.0], radius: 1.0};
+        let b = AxisEase(a, [Easing::Linear, Easing::Linear]);
+        assert!(checku(&b));
+        let mut s = 0.0;
+        loop {
+            assert_eq!(b.hu(s), a.hu(s));
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+
+        let line = Square::new(Lerp(0.0, 1.0), Lerp(0.0, 1.0)).into_as_vec().into_diagonal();
+        let c = AxisEase(line, [Easing::EaseOut(2), Easing::Linear]);
+        assert!(checku(&c));
+        assert_eq!(c.hu(0.5), [0.75, 0.5]);
+    }
+
+    #[test]
+    fn check_clamp_box() {
+        // Moves from `[0.0, 0.0]` to `[2.0, 1.0]`, overshooting the unit box along x.
+        let a = Lerp(0.0, 2.0).into_map(|x| [x, x * 0.5]);
+        let b = ClampBox(a, [0.0, 0.0], [1.0, 1.0]);
+        assert!(checku(&b));
+        assert_eq!(b.hu(0.25), [0.5, 0.25]);
+        assert_eq!(b.hu(0.75), [1.0, 0.75]);
+        assert_eq!(b.hu(1.0), [1.0, 1.0]);
+    }
+
+    #[test]
+    fn check_player() {
+        let mut p = Player::new(Lerp(0.0, 10.0));
+        assert_eq!(p.advance(0.25), 2.5);
+        assert_eq!(p.advance(0.5), 7.5);
+        assert_eq!(p.advance(0.5), 10.0);
+        assert_eq!(p.position, 1.0);
+        p.speed = -0.5;
+        assert_eq!(p.advance(1.0), 5.0);
+        assert_eq!(p.position, 0.5);
+        assert_eq!(p.advance(2.0), 0.0);
+        assert_eq!(p.position, 0.0);
+    }
+
+    #[test]
+    fn check_control_centroid() {
+        let qb = QuadraticBezier(0.0, 3.0, 0.0);
+        assert_eq!(qb.control_centroid(), 1.0);
+
+        // A symmetric cubic has its centroid at the geometric center.
+        let cb = CubicBezier(0.0, 1.0, 3.0, 4.0);
+        assert_eq!(cb.control_centroid(), 2.0);
+    }
+
+    #[test]
+    fn check_centroid_at() {
+        let qb = QuadraticBezier(0.0, 3.0, 0.0);
+        assert_eq!(qb.centroid_at(0.0), 1.0);
+        assert_eq!(qb.centroid_at(0.5), 1.5);
+
+        let cb = CubicBezier(0.0, 1.0, 3.0, 4.0);
+        assert_eq!(cb.centroid_at(0.5), 2.0);
+        assert_eq!(cb.centroid_at(1.0), (1.0 + 3.0 + 4.0 + 3.0 + 4.0 + 4.0) / 6.0);
+    }
+
+    #[test]
+    fn check_h_slice() {
+        let a = Square::new(Lerp(1.0, 5.0), Lerp(11.0, 15.0));
+        assert_eq!(a.h_slice(((), ()), &[0.5, 0.5]), (3.0, 13.0));
+        assert_eq!(a.h_slice(((), ()), &[0.5, 0.25]), a.hu([0.5, 0.25]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_h_slice_wrong_dimension() {
+        let a = Square::new(Lerp(1.0, 5.0), Lerp(11.0, 15.0));
+        let _ = a.h_slice(((), ()), &[0.5, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn check_polar_mesh() {
+        fn area(a: [f64; 3], b: [f64; 3], c: [f64; 3]) -> f64 {
+            let u = [b[0] - a[0], b[1] - a[1]];
+            let v = [c[0] - a[0], c[1] - a[1]];
+            0.5 * (u[0] * v[1] - u[1] * v[0])
+        }
+
+        let circle = Circle {center: [0.0, 0.0], radius: 1.0};
+        let scale = |(r, p): (f64, [f64; 2])| [r * p[0], r * p[1], 0.0];
+        let disc = Square::new(Lerp(0.0, 1.0), circle).into_map(scale);
+        let (vertices, triangles) = polar_mesh(&disc, 4, 8);
+        assert_eq!(vertices.len(), 1 + 4 * 8);
+        assert_eq!(triangles.len(), 8 + 3 * 8 * 2);
+        for t in &triangles {
+            let a = area(vertices[t[0] as usize], vertices[t[1] as usize], vertices[t[2] as usize]);
+            assert!(a > 0.0);
+        }
+
+        let annulus = Square::new(Lerp(1.0, 2.0), circle).into_map(scale);
+        let (vertices, triangles) = polar_mesh(&annulus, 4, 8);
+        assert_eq!(vertices.len(), 5 * 8);
+        assert_eq!(triangles.len(), 4 * 8 * 2);
+    }
+
+    #[test]
+    fn check_curvature() {
+        let a = Lerp(0.0, 1.0).into_map(|x| [x, 2.0 * x]);
+        assert!(acceleration(&a, (), 0.5, 0.001)[0].abs() < 0.000001);
+        assert!(curvature(&a, (), 0.5, 0.001) < 0.000001);
+        assert!(curvature(&a, (), 0.0, 0.001) < 0.000001);
+
+        let b = Circle {center: [0.0, 0.0], radius: 2.0};
+        assert!((curvature(&b, (), 0.3, 0.0001) - 0.5).abs() < 0.0001);
+    }
+
+    #[test]
+    fn check_signed_area() {
+        use std::f64::consts::PI;
+
+        let a = Circle {center: [1.0, 2.0], radius: 2.0};
+        assert!((signed_area(&a, 1000) - PI * 4.0).abs() < 0.001);
+        assert!(is_ccw(&a, 1000));
+
+        let b = a.inverse();
+        assert!((signed_area(&b, 1000) + PI * 4.0).abs() < 0.001);
+        assert!(!is_ccw(&b, 1000));
+    }
+
+    #[test]
+    fn check_squad() {
+        let h = 0.5f64.sqrt();
+        let a = Squad(
+            [0.0, 0.0, 0.0, 1.0],
+            [0.0, 0.0, 0.6, 0.8],
+            [0.0, 0.6, 0.0, 0.8],
+            [h, 0.0, 0.0, h],
+        );
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.0), [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(a.hu(1.0), [h, 0.0, 0.0, h]);
+        let mut s = 0.0;
+        loop {
+            let q = a.hu(s);
+            assert!((q[0] * q[0] + q[1] * q[1] + q[2] * q[2] + q[3] * q[3] - 1.0).abs() < 1e-9);
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+    }
+
+    #[test]
+    fn check_blend_svg_paths() {
+        let a = "M 0 0 L 10,20 Q 0 0 -4e1 8";
+        let b = "M10 10L30 40Q 2 2 0 0";
+        assert_eq!(blend_svg_paths(a, b, 0.0), Ok("M 0 0 L 10 20 Q 0 0 -40 8".into()));
+        assert_eq!(blend_svg_paths(a, b, 0.5), Ok("M 5 5 L 20 30 Q 1 1 -20 4".into()));
+        assert_eq!(blend_svg_paths(a, "M 0 0 L 1 1", 0.5), Err(SvgParseError::MismatchedStructure));
+        assert_eq!(blend_svg_paths(a, "M 0 0 C 1 1 Q 1 1 1 1", 0.5),
+                   Err(SvgParseError::MismatchedStructure));
+        assert_eq!(blend_svg_paths("1 2", "1 2", 0.5), Err(SvgParseError::MissingCommand));
+        assert_eq!(blend_svg_paths("M 1..2", "M 1 0.2", 0.5), Ok("M 1 0.2".into()));
+        assert_eq!(blend_svg_paths("M0.5.5", "M 0.5 0.5", 0.0), Ok("M 0.5 0.5".into()));
+        assert_eq!(blend_svg_paths("M 1e-1.5", "M 0.1 0.5", 1.0), Ok("M 0.1 0.5".into()));
+        assert_eq!(blend_svg_paths("M 1e", "M 1 2", 0.5),
+                   Err(SvgParseError::InvalidNumber("1e".into())));
+    }
+
+    #[test]
+    fn check_draw_on() {
+        let a = Circle {center: [0.0, 0.0], radius: 1.0};
+        let b = DrawOn(a, 4);
+        assert!(checku(&b));
+        assert_eq!(b.hu(0.0), vec![[1.0, 0.0]]);
+        assert_eq!(b.hu(0.5), vec![[1.0, 0.0], [0.0, 1.0], [-1.0, 0.0]]);
+        assert_eq!(b.hu(1.0), vec![[1.0, 0.0], [0.0, 1.0], [-1.0, 0.0], [0.0, -1.0], [1.0, 0.0]]);
+        assert_eq!(b.hu(0.6).len(), 4);
+    }
+
+    #[test]
+    fn check_gradient_morph() {
+        let a = vec![(0.0, [0.0; 3]), (1.0, [1.0; 3])];
+        let b = vec![(0.0, [1.0, 0.0, 0.0]), (0.5, [0.0, 1.0, 0.0]), (1.0, [0.0, 0.0, 1.0])];
+        let c = GradientMorph::new(a, b);
+        assert!(check(&c, 0.25));
+        assert_eq!(c.color_at(0.5, 0.0), [0.5; 3]);
+        assert_eq!(c.color_at(0.5, 1.0), [0.0, 1.0, 0.0]);
+        assert_eq!(c.color_at(0.5, 0.5), [0.25, 0.75, 0.25]);
+        assert_eq!(c.color_at(0.0, 0.5), [0.5, 0.0, 0.0]);
+
+        let stops = c.stops_at(0.5);
+        assert_eq!(stops.len(), 3);
+        assert_eq!(stops[1], (0.5, [0.25, 0.75, 0.25]));
+    }
+
+    #[test]
+    fn check_slice_z() {
+        let a = Cube::new(Lerp(-1.0, 1.0), Lerp(-1.0, 1.0), Lerp(-1.0, 1.0));
+        let field = a.into_map(|(x, y, z): (f64, f64, f64)| (x * x + y * y + z * z).sqrt());
+        let res = [5, 7];
+        let slice = slice_z(&field, 0.25, res);
+        assert_eq!(slice.len(), 35);
+        for j in 0..res[1] as usize {
+            for i in 0..res[0] as usize {
+                let v = slice[j * 5 + i];
+                assert!((v - slice[j * 5 + 4 - i]).abs() < 1e-12);
+                assert!((v - slice[(6 - j) * 5 + i]).abs() < 1e-12);
+            }
+        }
+        assert_eq!(slice[3 * 5 + 2], 0.5);
+    }
+
+    #[test]
+    fn check_variable_width() {
+        let spine = Lerp(0.0, 10.0).into_map(|x| [x, 0.0]);
+        let a = VariableWidth(spine, Lerp(1.0, 1.0));
+        let outline = a.outline(4);
+        assert_eq!(outline.len(), 10);
+        assert_eq!(outline[0], [0.0, 1.0]);
+        assert_eq!(outline[4], [10.0, 1.0]);
+        assert_eq!(outline[5], [10.0, -1.0]);
+        assert_eq!(outline[9], [0.0, -1.0]);
+        for p in &outline {
+            assert!(p[0] >= 0.0 && p[0] <= 10.0);
+            assert_eq!(p[1].abs(), 1.0);
+        }
+    }
+
+    #[test]
+    fn check_cached_homotopy() {
+        let a = QuadraticBezier(0.3, 0.7, 0.9);
+        let mut b = CachedHomotopy::new(a, 3);
+        assert!(b.is_empty());
+        for &s in &[0.0, 0.5, 0.0, 0.25, 0.75, 0.5, 1.0, 0.5] {
+            assert_eq!(b.h_cached((), s), a.hu(s));
+            assert!(b.len() <= 3);
+        }
+        assert_eq!(b.len(), 3);
+
+        // Changing the homotopy clears stale results.
+        b.homotopy_mut().2 = 10.0;
+        assert!(b.is_empty());
+        assert_eq!(b.h_cached((), 1.0), 10.0);
+        assert_eq!(b.get_ref().2, 10.0);
+        assert_eq!(b.into_inner().0, a.0);
+    }
+
+    #[test]
+    fn check_adsr_morph() {
+        let a = Adsr {attack: 0.1, decay: 0.2, sustain: 0.5, release: 1.0};
+        let b = Adsr {attack: 0.3, decay: 0.4, sustain: 0.7, release: 2.0};
+        let c = AdsrMorph(a, b);
+        assert_eq!(c.hu(0.0).attack, 0.1);
+        assert_eq!(c.hu(1.0).attack, 0.3);
+        assert!((c.hu(0.5).attack - 0.2).abs() < 1e-12);
+        assert!((c.hu(0.5).release - 1.5).abs() < 1e-12);
+
+        assert!((c.amplitude_at(0.1, 0.5) - 0.5).abs() < 1e-12);
+        assert!((c.amplitude_at(0.2, 0.5) - 1.0).abs() < 1e-12);
+        assert!((c.amplitude_at(10.0, 0.5) - 0.6).abs() < 1e-12);
+        assert_eq!(a.release_amplitude(0.5), 0.25);
+    }
+
+    #[test]
+    fn check_geo_slerp() {
+        use std::f64::consts::PI;
+
+        let a = GeoSlerp {from: [0.0, 0.0], to: [0.0, PI * 0.5]};
+        assert!(checku(&a));
+        let mut s = 0.0;
+        loop {
+            let p = a.hu(s);
+            assert!(p[0].abs() < 1e-12);
+            assert!((p[1] - s * PI * 0.5).abs() < 1e-12);
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+
+        let b = GeoSlerp {from: [0.0, 0.0], to: [0.0, PI]};
+        assert!((b.hu(0.5)[0] - PI * 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn check_diagonal_equivalence() {
+        let a = Lerp(1.0, 5.0);
+        let b = Lerp(-2.0, 3.0);
+        assert!(check_diagonal_equiv(&a, &b, 10));
+        let c = QuadraticBezier(0.0, 2.0, 1.0);
+        let d = Circle {center: [0.0; 2], radius: 1.0};
+        assert!(check_diagonal_equiv(&c, &d, 10));
+    }
+
+    #[test]
+    fn check_cube_ease() {
+        let a = Cube::new(Lerp(0.0, 1.0), Lerp(0.0, 1.0), Lerp(0.0, 1.0));
+        let b = CubeEase(a, [Easing::Linear; 3]);
+        assert!(checku3(&b));
+        assert_eq!(b.hu([0.5, 0.25, 0.75]), a.hu([0.5, 0.25, 0.75]));
+
+        let c = CubeEase(a, [Easing::Linear, Easing::Linear, Easing::EaseIn(2)]);
+        assert!(checku3(&c));
+        assert_eq!(c.hu([0.5; 3]), (0.5, 0.5, 0.25));
+    }
+
+    #[test]
+    fn check_table_lerp() {
+        let n = 1000;
+        let table = |fun: fn(f64) -> f64| -> Vec<f64> {
+            (0..n + 1).map(|i| fun(i as f64 / n as f64)).collect()
+        };
+        let a = TableLerp::new(table(f64::sin), table(f64::cos));
+        assert!(check(&a, 0.3));
+        for &x in &[0.0f64, 0.123, 0.5, 0.999, 1.0] {
+            for &s in &[0.0, 0.3, 1.0] {
+                let expected = x.sin() * (1.0 - s) + x.cos() * s;
+                assert!((a.h(x, s) - expected).abs() < 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_table_lerp_mismatched_lengths() {
+        let _ = TableLerp::new(vec![0.0, 1.0], vec![0.0]);
+    }
+
+    #[test]
+    fn check_tube_mesh() {
+        let spine = Lerp(0.0, 10.0).into_map(|z| [1.0, 2.0, z]);
+        let (vertices, triangles) = tube_mesh(&spine, 0.5, 4, 8);
+        assert_eq!(vertices.len(), 5 * 8);
+        assert_eq!(triangles.len(), 4 * 8 * 2);
+        for (k, v) in vertices.iter().enumerate() {
+            let d = ((v[0] - 1.0).powi(2) + (v[1] - 2.0).powi(2)).sqrt();
+            assert!((d - 0.5).abs() < 1e-12);
+            assert!((v[2] - (k / 8) as f64 * 2.5).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn check_min_torque_lerp() {
+        let id = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        // A quarter turn about the z-axis.
+        let rz = [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+        let a = MinTorqueLerp(id, rz);
+        assert!(check(&a, [1.0, 2.0, 3.0]));
+        assert_eq!(a.h([1.0, 0.0, 0.0], 0.0), [1.0, 0.0, 0.0]);
+        assert_eq!(a.h([1.0, 0.0, 0.0], 1.0), [0.0, 1.0, 0.0]);
+
+        let m = a.matrix_at(0.5);
+        for i in 0..3 {
+            for j in 0..3 {
+                let d = m[i][0] * m[j][0] + m[i][1] * m[j][1] + m[i][2] * m[j][2];
+                assert!((d - if i == j {1.0} else {0.0}).abs() < 1e-12);
+            }
+        }
+        let p = a.h([1.0, 0.0, 0.0], 0.5);
+        let h = 0.5f64.sqrt();
+        assert!((p[0] - h).abs() < 1e-12 && (p[1] - h).abs() < 1e-12 && p[2].abs() < 1e-12);
+
+        // A half turn about the x-axis, starting from the quarter turn.
+        let rzx = [[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, -1.0]];
+        let b = MinTorqueLerp(rz, rzx);
+        let p = b.h([0.0, 0.0, 1.0], 0.999999);
+        assert!((p[2] + 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn check_heightmap_morph() {
+        let a = vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+        let b = vec![0.0, 4.0, 0.0, 0.0, 2.0, 0.0];
+        let mut c = HeightmapMorph {a, b, width: 3, sharpen: 0.0};
+        assert!(check(&c, [1, 0]));
+        for y in 0..2 {
+            for x in 0..3 {
+                assert_eq!(c.h([x, y], 0.5), c.b[(y * 3 + x) as usize] * 0.5);
+            }
+        }
+
+        // The ridge of `b` is steeper, so it dominates the blend when sharpening.
+        c.sharpen = 1.0;
+        assert!(check(&c, [1, 0]));
+        assert_eq!(c.h([0, 0], 0.5), 0.0);
+        assert!(c.h([1, 0], 0.5) > 2.0);
+    }
+
+    #[test]
+    fn check_repeat_mirror() {
+        let a = Lerp(0.0, 1.0).into_map(|t| [0.2 + 0.4 * t, 0.2]);
+        let b = RepeatMirror(&a, 1);
+        assert!(checku(&b));
+        assert_eq!(b.hu(0.5), a.hu(0.5));
+
+        let c = RepeatMirror(&a, 2);
+        assert_eq!(c.hu(0.0), [0.1, 0.1]);
+        assert_eq!(c.hu(0.125), [0.2, 0.1]);
+        assert_eq!(c.hu(0.375), [0.8, 0.1]);
+        assert_eq!(c.hu(0.625), [0.2, 0.9]);
+        assert_eq!(c.hu(0.875), [0.8, 0.9]);
+        assert_eq!(c.hu(1.0), [0.7, 0.9]);
+    }
+
+    #[test]
+    fn check_inflection_points() {
+        let a = CubicBezier([0.0, 0.0], [1.0, 1.0], [2.0, -1.0], [3.0, 0.0]);
+        assert_eq!(a.inflection_points(), vec![0.5]);
+
+        let b = CubicBezier([0.0, 0.0], [1.0, 1.0], [2.0, 1.0], [3.0, 0.0]);
+        assert_eq!(b.inflection_points(), vec![]);
+    }
+
+    #[test]
+    fn check_polygon_morph() {
+        let triangle = vec![[0.0, 0.0], [4.0, 0.0], [0.0, 3.0]];
+        let square = vec![[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]];
+        let a = PolygonMorph(triangle, square.clone());
+        assert!(checku(&a));
+        assert_eq!(a.hu(1.0), square);
+        // The triangle has perimeter 12, so it is resampled every 3 units.
+        let near = |a: Vec<[f64; 2]>, b: Vec<[f64; 2]>| {
+            a.len() == b.len() &&
+            a.iter().zip(b.iter()).all(|(p, q)| (p[0] - q[0]).abs() + (p[1] - q[1]).abs() < 1e-12)
+        };
+        assert!(near(a.hu(0.0), vec![[0.0, 0.0], [3.0, 0.0], [2.4, 1.2], [0.0, 3.0]]));
+        assert!(near(a.hu(0.5), vec![[0.0, 0.0], [2.5, 0.0], [2.2, 1.6], [0.0, 2.5]]));
+    }
+
+    #[test]
+    fn check_smooth() {
+        let step = Lerp(0.0, 1.0).into_map(|t| if t < 0.5 {0.0} else {1.0});
+        let a = Smooth(step, 0.1, 101);
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.2), 0.0);
+        assert_eq!(a.hu(0.8), 1.0);
+        assert!((a.hu(0.45) - 0.25).abs() < 0.02);
+        assert!((a.hu(0.5) - 0.5).abs() < 0.02);
+        assert!((a.hu(0.55) - 0.75).abs() < 0.02);
+    }
+
+    #[test]
+    fn check_with_alpha() {
+        let position = CubicBezier(0.0, 0.2, 0.8, 1.0).into_map(|x| [x, 1.0 - x]);
+        let alpha = QuadraticBezier(0.0, 0.0, 1.0);
+        let a = WithAlpha(&position, alpha);
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.5), (position.hu(0.5), 0.25));
+        assert_eq!(a.to_rgba([1.0, 0.5, 0.0], 0.5), [1.0, 0.5, 0.0, 0.25]);
+    }
+
+    #[test]
+    fn check_jitter() {
+        let a = Circle {center: [0.0, 0.0], radius: 1.0};
+        let b = Jitter(a, 7, 0.01);
+        let c = Jitter(a, 7, 0.01);
+        let d = Jitter(a, 8, 0.01);
+        assert!(checku(&b));
+        for &s in &[0.0, 0.1, 0.5, 0.9, 1.0] {
+            let p = b.hu(s);
+            assert_eq!(p, c.hu(s));
+            assert!(p != d.hu(s));
+            let q = a.hu(s);
+            assert!((p[0] - q[0]).abs() <= 0.01 && (p[1] - q[1]).abs() <= 0.01);
+        }
+        assert_eq!(b.h((), -0.0), b.f(()));
+    }
+
+    #[test]
+    fn check_aabb_lerp() {
+        let a = AabbLerp {from: ([0.0, 0.0], [2.0, 2.0]), to: ([-1.0, 1.0], [5.0, 3.0])};
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.5), ([-0.5, 0.5], [3.5, 2.5]));
+        assert_eq!(a.rect_at(0.5), [-0.5, 0.5, 4.0, 2.0]);
+    }
+
+    #[test]
+    fn check_table_reparam() {
+        let a = Lerp(2.0, 4.0);
+        let b = TableReparam::from_easing(a, Easing::EaseInOut(3), 200);
+        assert!(checku(&b));
+        let mut s = 0.0;
+        loop {
+            let expected = a.hu(Easing::EaseInOut(3).apply(s));
+            assert!((b.hu(s) - expected).abs() < 0.001);
+            s += 0.05;
+            if s > 1.0 {break}
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_table_reparam_not_monotonic() {
+        let _ = TableReparam::new(Lerp(0.0, 1.0), vec![0.0, 0.6, 0.4, 1.0]);
+    }
+
+    #[test]
+    fn check_checker() {
+        let a = Square::new(Lerp(0.0, 1.0), Lerp(0.0, 1.0));
+        let b = Square::new(Lerp(10.0, 11.0), Lerp(10.0, 11.0));
+        let c = Checker(a, b, 2);
+        assert!(checku2(&c));
+        assert_eq!(c.hu([0.25, 0.25]), (0.25, 0.25));
+        assert_eq!(c.hu([0.75, 0.25]), (10.75, 10.25));
+        assert_eq!(c.hu([0.25, 0.75]), (10.25, 10.75));
+        assert_eq!(c.hu([0.75, 0.75]), (0.75, 0.75));
+    }
+
+    #[test]
+    fn check_closest_param() {
+        let a = Lerp(0.0, 1.0).into_map(|t| [4.0 * t, 2.0 * t]);
+        let s = closest_param(&a, (), [2.0, 1.0], 7, 40);
+        assert!((s - 0.5).abs() < 1e-6);
+        let s = closest_param(&a, (), [3.0, -1.0], 7, 40);
+        assert!((s - 0.5).abs() < 1e-6);
+        assert_eq!(closest_param(&a, (), [-5.0, 0.0], 7, 40), 0.0);
+
+        let b = Circle {center: [0.0, 0.0], radius: 1.0};
+        let s = closest_param(&b, (), [0.0, 2.0], 10, 40);
+        assert!((s - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn check_frame_seq() {
+        let a = FrameSeq {count: 8};
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.0), 0);
+        assert_eq!(a.hu(0.124), 0);
+        assert_eq!(a.hu(0.125), 1);
+        assert_eq!(a.hu(0.5), 4);
+        assert_eq!(a.hu(1.0), 7);
+
+        let b = FrameSeq {count: 0};
+        assert!(checku(&b));
+        assert_eq!(b.hu(0.5), 0);
+    }
+
+    #[test]
+    fn check_fold_samples() {
+        let a = Lerp(0.0, 1.0);
+        let sum = fold_samples(&a, (), 10, 0.0, |acc, _, y| acc + y);
+        assert!((sum - 0.5 * 11.0).abs() < 1e-12);
+        let max = fold_samples(&a, (), 10, (0.0, 0.0), |acc, s, y| {
+            if y > acc.1 {(s, y)} else {acc}
+        });
+        assert_eq!(max, (1.0, 1.0));
+    }
+
+    #[test]
+    fn check_ease_blend() {
+        let a = EaseBlend(Easing::Linear, Easing::Linear);
+        let b = EaseBlend(Easing::Linear, Easing::SmoothStep);
+        assert!(check(&a, 0.3));
+        assert!(check(&b, 0.3));
+        for &x in &[0.0, 0.25, 0.5, 0.75, 1.0] {
+            for &s in &[0.0, 0.5, 1.0] {
+                assert_eq!(a.h(x, s), x);
+            }
+            assert_eq!(b.h(x, 0.5), 0.5 * (x + Easing::SmoothStep.apply(x)));
+        }
+        assert_eq!(b.h(0.25, 0.5), 0.5 * (0.25 + 0.15625));
+    }
+
+    #[test]
+    fn check_retime() {
+        let a = QuadraticBezier(1.0, 4.0, 2.0);
+        let b = Retime(a, Lerp(0.0, 1.0));
+        assert!(checku(&b));
+        let mut s = 0.0;
+        loop {
+            assert_eq!(b.hu(s), a.hu(s));
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+
+        let c = Retime(Lerp(0.0, 10.0), QuadraticBezier(0.0, 0.0, 1.0));
+        assert!(checku(&c));
+        assert_eq!(c.hu(0.5), 2.5);
+    }
+
+    #[test]
+    fn check_matrix_lerp() {
+        let id = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        let rz = [[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+        let mut a = MatrixLerp {a: id, b: rz, orthonormalize: true};
+        assert!(check(&a, [1.0, 2.0, 3.0]));
+        let p = [1.0, 2.0, 3.0];
+        let len = |p: [f64; 3]| (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
+        for &s in &[0.25, 0.5, 0.75] {
+            assert!((len(a.h(p, s)) - len(p)).abs() < 1e-12);
+        }
+
+        a.orthonormalize = false;
+        assert!(len(a.h(p, 0.5)) < len(p) - 0.1);
+    }
+
+    #[test]
+    fn check_param_morph() {
+        use std::f64::consts::PI;
+
+        let line = |u: f64| [1.0 - 2.0 * u, 0.0];
+        let semicircle = |u: f64| [(PI * u).cos(), (PI * u).sin()];
+        let a = ParamMorph(line, semicircle);
+        assert!(check(&a, 0.5));
+        assert_eq!(a.h(0.5, 0.0), [0.0, 0.0]);
+        assert_eq!(a.h(0.0, 1.0), [1.0, 0.0]);
+        let mid = a.h(0.5, 0.5);
+        assert!(mid[0].abs() < 1e-12);
+        assert_eq!(mid[1], 0.5);
+    }
+
+    #[test]
+    fn check_flow_field() {
+        let a = Translate([2.0, -1.0]);
+        let field = flow_field(&a, [4, 3], 0.5, 0.001);
+        assert_eq!(field.len(), 12);
+        for v in field {
+            assert!((v[0] - 2.0).abs() < 1e-9);
+            assert!((v[1] + 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn check_wasserstein_morph() {
+        let n = 100;
+        let gaussian = |mean: f64| -> Vec<f64> {
+            (0..n).map(|i| {
+                let x = (i as f64 + 0.5) / n as f64;
+                (-(x - mean) * (x - mean) / (2.0 * 0.05 * 0.05)).exp()
+            }).collect()
+        };
+        let moments = |bins: &[f64]| -> (f64, f64, f64) {
+            let total: f64 = bins.iter().sum();
+            let center = |i: usize| (i as f64 + 0.5) / n as f64;
+            let mean = bins.iter().enumerate().map(|(i, m)| center(i) * m).sum::<f64>() / total;
+            let var = bins.iter().enumerate()
+                .map(|(i, m)| (center(i) - mean) * (center(i) - mean) * m).sum::<f64>() / total;
+            (total, mean, var.sqrt())
+        };
+        let a = WassersteinMorph(gaussian(0.3), gaussian(0.7));
+        assert!(check(&a, 30));
+
+        let (total0, _, sigma0) = moments(&a.0);
+        let (total, mean, sigma) = moments(&a.bins_at(0.5));
+        assert!((total - total0).abs() < 1e-9);
+        assert!((mean - 0.5).abs() < 1e-3);
+        assert!((sigma - sigma0).abs() < 1e-3);
+        // Linear blending would produce two bumps with a dip in the middle.
+        assert!(a.h(50, 0.5) > a.h(30, 0.5));
+    }
+
+    #[test]
+    fn check_bounded() {
+        let a = Bounded(Square::new(Lerp(0.0, 1.0), Lerp(2.0, 4.0)));
+        assert!(check2(&a, ((), ())));
+        assert_eq!(a.try_h(((), ()), [0.5, 0.5]), Ok((0.5, 3.0)));
+        assert_eq!(a.try_h(((), ()), [1.5, 0.5]), Err(OutOfBounds {index: 0, value: 1.5}));
+        assert!(Bounded(Lerp(0.0, 1.0)).try_h((), -0.1).is_err());
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_bounded_panics() {
+        Bounded(Lerp(0.0, 1.0)).h((), 1.5);
+    }
+
+    #[test]
+    fn check_fix_axes() {
+        let a = Cube::new(Lerp(0.0, 1.0), Lerp(2.0, 4.0), Lerp(5.0, 8.0));
+        let x = ((), (), ());
+        let b = a.fix_axes::<f64>(&[(0, 0.5), (2, 1.0)]);
+        assert!(check(&b, x));
+        assert_eq!(b.h(x, 0.0), (0.5, 2.0, 8.0));
+        assert_eq!(b.h(x, 0.5), (0.5, 3.0, 8.0));
+        assert_eq!(b.h(x, 1.0), (0.5, 4.0, 8.0));
+
+        let c = a.fix_axes::<[f64; 2]>(&[(1, 0.5)]);
+        assert!(check2(&c, x));
+        assert_eq!(c.h(x, [0.5, 0.0]), (0.5, 3.0, 5.0));
+    }
+
+    #[test]
+    fn check_odometer() {
+        let a = Odometer(10.0, 20.0, 0.1);
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.0), 10.0);
+        assert_eq!(a.hu(1.0), 20.0);
+        assert_eq!(a.hu(0.05).fract(), 0.0);
+        assert_eq!(a.hu(0.95).fract(), 0.0);
+        assert_eq!(a.hu(0.5), 15.0);
+        let v = a.hu(0.3);
+        assert_eq!(v, 10.0 + 10.0 * Easing::SmoothStep.apply(0.3));
+        assert!(v.fract() != 0.0);
+    }
+
+    #[test]
+    fn check_hodograph() {
+        let a = QuadraticBezier(0.0, 3.0, 1.0);
+        let b = a.hodograph();
+        let mut s = 0.0;
+        loop {
+            assert!((b.hu(s) - tangent(&a, (), s, 1e-6)).abs() < 1e-4);
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+
+        // Compare with the derivative of the Bernstein form.
+        let (p0, p1, p2, p3) = (0.0, 2.0, -1.0, 1.0);
+        let a = CubicBezier(p0, p1, p2, p3);
+        let b = a.hodograph();
+        let mut s = 0.0;
+        loop {
+            let t = 1.0 - s;
+            let d = 3.0 * t * t * (p1 - p0) + 6.0 * t * s * (p2 - p1) + 3.0 * s * s * (p3 - p2);
+            assert!((b.hu(s) - d).abs() < 1e-12);
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+    }
+
+    #[test]
+    fn check_camera_morph() {
+        let from = Camera {eye: [0.0, 0.0, 5.0], target: [0.0; 3], up: [0.0, 1.0, 0.0]};
+        let to = Camera {eye: [5.0, 0.0, 0.0], target: [0.0; 3], up: [0.0, 1.0, 0.0]};
+        let a = CameraMorph {from, to};
+        assert!(checku(&a));
+        assert_eq!(a.view_matrix_at(0.0), from.view_matrix());
+        assert_eq!(a.view_matrix_at(1.0), to.view_matrix());
+        assert_eq!(from.view_matrix()[2], [0.0, 0.0, 1.0, -5.0]);
+
+        // Halfway, the camera looks diagonally at the origin from the lerped eye.
+        let m = a.view_matrix_at(0.5);
+        let d = 0.5f64.sqrt();
+        assert!((m[2][0] - d).abs() < 1e-12 && (m[2][2] - d).abs() < 1e-12);
+        assert!((m[1][1] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn check_piecewise() {
+        let linear: CubicBezier<f64> = QuadraticBezier::from(Lerp(0.0, 1.0)).into();
+        let cubic = CubicBezier(1.0, 3.0, -1.0, 2.0);
+        let a = Piecewise::new(vec![0.0, 0.25, 1.0], vec![linear, cubic]);
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.0), 0.0);
+        assert_eq!(a.hu(0.25), 1.0);
+        assert_eq!(a.hu(1.0), 2.0);
+        assert!((a.hu(0.125) - 0.5).abs() < 1e-12);
+        assert_eq!(a.hu(0.625), cubic.hu(0.5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_piecewise_unsorted_breaks() {
+        Piecewise::new(vec![0.0, 0.75, 0.5, 1.0], vec![Lerp(0.0, 1.0); 3]);
+    }
+
+    #[test]
+    fn check_eased_circle() {
+        let a = Circle {center: [0.0, 0.0], radius: 1.0};
+        let b = a.eased(Easing::Linear);
+        let mut s = 0.0;
+        loop {
+            assert_eq!(b.hu(s), a.hu(s));
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+
+        // Ease-out spins fast at first, so it passes the halfway angle early.
+        let c = a.eased(Easing::EaseOut(2));
+        assert!(checku(&c));
+        assert!(a.hu(0.3)[1] > 0.0);
+        assert!(c.hu(0.3)[1] < 0.0);
+    }
+
+    #[test]
+    fn check_map() {
+        let a = Square::new(Lerp(0.0, 1.0), Lerp(2.0, 4.0));
+        let b = a.map(|(x, y)| [x, y, x + y]);
+        assert!(check2(&b, ((), ())));
+        assert_eq!(b.hu([0.5, 0.5]), [0.5, 3.0, 3.5]);
+        assert_eq!(b.f(((), ())), [0.0, 2.0, 2.0]);
+        assert_eq!(b.g(((), ())), [1.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn check_blend_normal() {
+        let n = blend_normal([1.0, 0.0, 0.0], [0.0, 1.0, 0.0], 0.5);
+        let d = 0.5f64.sqrt();
+        assert!((n[0] - d).abs() < 1e-12 && (n[1] - d).abs() < 1e-12 && n[2] == 0.0);
+        assert!((dot3(n, n) - 1.0).abs() < 1e-12);
+        assert_eq!(blend_normal([0.0, 0.0, 2.0], [0.0, 1.0, 0.0], 0.0), [0.0, 0.0, 1.0]);
+
+        let n = blend_normal([0.0, 0.0, 1.0], [0.0, 0.0, -1.0], 0.5);
+        assert!((dot3(n, n) - 1.0).abs() < 1e-12);
+        assert!(n[2].abs() < 1e-12);
+    }
+
+    #[test]
+    fn check_smap_hollow_cylinder() {
+        let inner_circle = Circle {center: [0.0, 0.0], radius: 1.0};
+        let outer_circle = Circle {center: [0.0, 0.0], radius: 2.0};
+        let inner_and_outer = Square::new(inner_circle, outer_circle);
+        let sweep = inner_and_outer.diagonal();
+        let hollow_cylinder = Square::new(sweep, Lerp(0.0, 10.0));
+        let map = hollow_cylinder.smap(|((a, b), c), s| [
+            a[0] + (b[0] - a[0]) * s,
+            a[1] + (b[1] - a[1]) * s,
+            c
+        ]);
+        assert!(checku3(&map));
+        assert_eq!(map.hu([0.0, 0.0, 0.0]), [1.0, 0.0, 0.0]);
+        assert_eq!(map.hu([0.0, 0.0, 1.0]), [2.0, 0.0, 0.0]);
+        assert_eq!(map.hu([0.25, 0.5, 0.5]), [0.0, 1.5, 5.0]);
+    }
+
+    #[cfg(feature = "glsl")]
+    #[test]
+    fn check_to_glsl() {
+        let a = Lerp([0.0, 1.0], [2.5, -3.0]);
+        let src = a.to_glsl("line");
+        assert!(src.starts_with("vec2 line(float s) {"));
+        assert!(src.contains("mix(vec2(0.0, 1.0), vec2(2.5, -3.0), s)"));
+
+        let b = CubicBezier([0.0, 0.0], [1.0, 1.0], [2.0, 1.0], [3.0, 0.0]);
+        assert!(b.to_glsl("curve").contains("vec2 b = mix(vec2(1.0, 1.0), vec2(2.0, 1.0), s);"));
+        let c = Circle {center: [1.0, 2.0], radius: 0.5};
+        let src = c.to_glsl("circle");
+        assert!(src.contains("return vec2(1.0, 2.0) + 0.5 * vec2(cos(a), sin(a));"));
+    }
+
+    #[test]
+    fn check_circle_off_center() {
+        let a = Circle {center: [3.0, -2.0], radius: 0.5};
+        assert!(checku(&a));
+        assert_eq!(a.f(()), a.g(()));
+        assert_eq!(a.hu(0.0), [3.5, -2.0]);
+        assert_eq!(a.hu(0.25), [3.0, -1.5]);
+        assert_eq!(a.hu(0.5), [2.5, -2.0]);
+        assert_eq!(a.hu(0.75), [3.0, -2.5]);
+        assert_eq!(a.hu(1.0), [3.5, -2.0]);
+    }
+
+    #[test]
+    fn check_segment_lengths() {
+        let to_point = |x: f64| [x, 2.0 * x];
+        let pieces = vec![Lerp(0.0, 1.0).into_map(to_point), Lerp(1.0, 2.0).into_map(to_point)];
+        let a = Piecewise::new(vec![0.0, 0.5, 1.0], pieces);
+        let lengths = a.segment_lengths(10);
+        assert_eq!(lengths.len(), 2);
+        assert!((lengths[0] - lengths[1]).abs() < 1e-12);
+        assert!((lengths[0] + lengths[1] - 2.0 * 5.0f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn check_resample_uniform() {
+        let a = CubicBezier(0.0, 0.05, 0.1, 1.0).into_map(|x| [x, x * x]);
+        let points = resample_uniform(&a, (), 10);
+        assert_eq!(points.len(), 10);
+        assert_eq!(points[0], [0.0, 0.0]);
+        assert_eq!(points[9], [1.0, 1.0]);
+        let dist = |p: [f64; 2], q: [f64; 2]| (p[0] - q[0]).hypot(p[1] - q[1]);
+        let first = dist(points[0], points[1]);
+        for w in points.windows(2) {
+            assert!((dist(w[0], w[1]) - first).abs() < 0.01 * first);
+        }
+    }
+
+    #[test]
+    fn check_hu_cylinder() {
+        let a = Circle {center: [0.0, 0.0], radius: 1.0};
+        let b = Lerp(0.0, 10.0);
+        let c = Square::new(a, b);
+        let d = c.map(|(xy, z)| [xy[0], xy[1], z]);
+        assert_eq!(d.hu([0.5, 0.5]), d.h(((), ()), [0.5, 0.5]));
+
+        let left = d.left();
+        assert_eq!(left.hu(0.5), [1.0, 0.0, 5.0]);
+        let top = d.top();
+        assert_eq!(top.hu(0.5), [-1.0, 0.0, 0.0]);
+        let bottom = d.bottom();
+        assert_eq!(bottom.hu(0.5), [-1.0, 0.0, 10.0]);
+        let middle = d.top_bottom(0.5);
+        assert_eq!(middle.hu(1.0), [1.0, 0.0, 5.0]);
+
+        let e = Cube::new(b, b, b).into_as_vec::<[f64; 3], [(); 3]>();
+        assert_eq!(e.hu([0.5, 0.5, 1.0]), [5.0, 5.0, 10.0]);
+        assert_eq!(e.hu([0.5, 0.5, 1.0]), e.h([(); 3], [0.5, 0.5, 1.0]));
+    }
+
+    #[test]
+    fn check_sdf_morph() {
+        let circle = Map::new(Id, |p: [f64; 2]| p[0].hypot(p[1]) - 1.0);
+        let square = Map::new(Id, |p: [f64; 2]| {
+            let d = [p[0].abs() - 0.5, p[1].abs() - 0.5];
+            d[0].max(0.0).hypot(d[1].max(0.0)) + d[0].max(d[1]).min(0.0)
+        });
+        let a = SdfMorph(circle, square);
+        assert!(check(&a, [0.3, 0.2]));
+
+        // Find the zero crossing along a ray from the origin.
+        let crossing = |dir: [f64; 2]| {
+            let (mut lo, mut hi) = (0.0, 2.0);
+            for _ in 0..60 {
+                let mid = 0.5 * (lo + hi);
+                if a.h([dir[0] * mid, dir[1] * mid], 0.5) < 0.0 {lo = mid} else {hi = mid}
+            }
+            lo
+        };
+        assert!((crossing([1.0, 0.0]) - 0.75).abs() < 1e-9);
+        // The square's corner is at `sqrt(0.5)` along the diagonal.
+        let d = 0.5f64.sqrt();
+        let r = crossing([d, d]);
+        assert!(r > d && r < 1.0);
+    }
+
+    #[test]
+    fn check_default_inputs() {
+        let a = Square::new(Lerp(0.0, 1.0), Circle {center: [0.0, 0.0], radius: 1.0});
+        assert!(check2(&a, Default::default()));
+        assert!(check2(&a, unit_input(&a)));
+        assert_eq!(unit_input(&a), ((), ()));
+
+        let b = Cube::new(Lerp(0.0, 1.0), Lerp(2.0, 3.0), Lerp(4.0, 5.0));
+        assert!(check3(&b, Default::default()));
+        assert!(checku3(&b));
+        assert_eq!(unit_input(&b), ((), (), ()));
+
+        let c = Square::new(a.into_diagonal(), Lerp(0.0, 10.0));
+        assert_eq!(unit_input(&c), (((), ()), ()));
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn check_render_frames() {
+        let dir = std::env::temp_dir().join("homotopy-render-frames");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let a = Circle {center: [0.0, 0.0], radius: 0.5};
+        let b = Circle {center: [0.0, 0.0], radius: 0.9};
+        let c = sweep(a, b);
+        let prefix = dir.join("sweep");
+        render_frames(&c, 5, 32, ([-1.0; 2], [1.0; 2]), 100, prefix.to_str().unwrap()).unwrap();
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 5);
+        assert!(dir.join("sweep-0004.png").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn check_smoothstep() {
+        let a = Smoothstep::from_lerp(Lerp(2.0, 6.0));
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.0), 2.0);
+        assert_eq!(a.hu(0.5), 4.0);
+        assert_eq!(a.hu(1.0), 6.0);
+        // `t(0.25) = 3 / 16 - 2 / 64 = 0.15625`.
+        assert_eq!(a.hu(0.25), 2.0 + 4.0 * 0.15625);
+    }
+
+    #[test]
+    fn check_phase_shift() {
+        let a = Circle {center: [0.0, 0.0], radius: 1.0};
+        let b = PhaseShift(a, 0.25);
+        assert!(checku(&b));
+        assert_eq!(b.hu(0.0), [0.0, 1.0]);
+        assert_eq!(b.hu(0.25), [-1.0, 0.0]);
+        assert_eq!(b.hu(1.0), [0.0, 1.0]);
+
+        let c = PhaseShift(a, -0.25);
+        assert_eq!(c.hu(0.0), [0.0, -1.0]);
+    }
+
+    #[test]
+    fn check_smootherstep() {
+        let a = Smootherstep(2.0, 6.0);
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.0), a.f(()));
+        assert_eq!(a.hu(1.0), a.g(()));
+        assert_eq!(a.hu(0.5), 4.0);
+        let mut d = 0.05;
+        loop {
+            assert!((a.hu(0.5 + d) - 4.0 - (4.0 - a.hu(0.5 - d))).abs() < 1e-12);
+            d += 0.05;
+            if d > 0.5 {break}
+        }
+        // Flatter than smoothstep near the ends.
+        assert!(a.hu(0.05) - 2.0 < Smoothstep(2.0, 6.0).hu(0.05) - 2.0);
+    }
+
+    #[test]
+    fn check_warp_morph() {
+        let res = [3, 4];
+        let grid: Vec<[f64; 2]> = (0..res[1]).flat_map(|j| (0..res[0]).map(move |i| {
+            [i as f64 / (res[0] - 1) as f64, j as f64 / (res[1] - 1) as f64]
+        })).collect();
+        let a = WarpMorph::new(grid.clone(), grid.clone(), res);
+        assert!(check(&a, [0.3, 0.7]));
+        for &uv in &[[0.0, 0.0], [0.3, 0.7], [0.5, 0.25], [1.0, 1.0]] {
+            let p = a.h(uv, 0.5);
+            assert!((p[0] - uv[0]).abs() < 1e-12 && (p[1] - uv[1]).abs() < 1e-12);
+        }
+
+        // Moving the grid halfway shifts the output halfway.
+        let shifted = grid.iter().map(|p| [p[0] + 1.0, p[1]]).collect();
+        let b = WarpMorph::new(grid, shifted, res);
+        let p = b.h([0.3, 0.7], 0.5);
+        assert!((p[0] - 0.8).abs() < 1e-12 && (p[1] - 0.7).abs() < 1e-12);
+    }
+
+    #[test]
+    fn check_ease() {
+        for n in 2..5 {
+            for &easing in &[Easing::EaseIn(n), Easing::EaseOut(n), Easing::EaseInOut(n)] {
+                let a = Ease(1.0, 3.0, easing);
+                assert!(checku(&a));
+                assert_eq!(a.hu(0.0), 1.0);
+                assert_eq!(a.hu(1.0), 3.0);
+                let mut prev = a.hu(0.0);
+                let mut s = 0.01;
+                loop {
+                    let y = a.hu(s);
+                    assert!(y >= prev);
+                    prev = y;
+                    s += 0.01;
+                    if s > 1.0 {break}
+                }
+            }
+        }
+        for &easing in &[Easing::EaseIn(0), Easing::EaseOut(0), Easing::EaseInOut(0)] {
+            let a = Ease(1.0, 3.0, easing);
+            assert!(checku(&a));
+            assert_eq!(a.hu(0.0), 1.0);
+            assert_eq!(a.hu(1.0), 3.0);
+        }
+        assert_eq!(Ease(0.0, 1.0, Easing::EaseIn(2)).hu(0.5), 0.25);
+        assert_eq!(Ease(0.0, 1.0, Easing::EaseOut(3)).hu(0.5), 0.875);
+        assert_eq!(Ease(0.0, 1.0, Easing::EaseInOut(4)).hu(0.25), 0.03125);
+        assert_eq!(Ease(0.0, 1.0, Easing::EaseInOut(4)).hu(0.5), 0.5);
+    }
+
+    #[test]
+    fn check_constant() {
+        let a = Constant(3.0);
+        assert!(check(&a, ()));
+        assert!(check(&a, [1.0, 2.0]));
+        assert_eq!(Homotopy::<(), [f64; 2]>::h(&a, (), [0.3, 0.7]), 3.0);
+
+        // Averaging with a constant halves the motion of a `Lerp`, pulling it toward the constant.
+        let b = Square::<(), (), _, _>::new(a, Lerp(0.0, 4.0))
+            .into_diagonal().into_map(|(c, l)| 0.5 * (c + l));
+        assert!(checku(&b));
+        assert_eq!(b.hu(0.0), 1.5);
+        assert_eq!(b.hu(0.5), 2.5);
+        assert_eq!(b.hu(1.0), 3.5);
+    }
+
+    #[test]
+    fn check_bezier() {
+        let qb = QuadraticBezier(0.3, 0.7, 0.9);
+        let a = Bezier::from(qb);
+        let cb = CubicBezier(0.3, 1.7, -0.8, 0.9);
+        let b = Bezier::from(cb);
+        assert!(checku(&a));
+        assert!(checku(&b));
+        let mut s = 0.0;
+        loop {
+            assert_eq!(a.hu(s), qb.hu(s));
+            assert_eq!(b.hu(s), cb.hu(s));
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+
+        let c = Bezier(vec![2.0]);
+        assert!(checku(&c));
+        assert_eq!(c.hu(0.5), 2.0);
+    }
+
+    #[test]
+    fn check_bezier_centroid() {
+        let qb = QuadraticBezier(0.0, 3.0, 0.0);
+        let cb = CubicBezier(0.0, 1.0, 3.0, 4.0);
+        let a = Bezier::from(qb);
+        let b = Bezier::from(cb);
+        assert!((a.control_centroid() - qb.control_centroid()).abs() < 1e-12);
+        assert!((b.control_centroid() - cb.control_centroid()).abs() < 1e-12);
+        let mut s = 0.0;
+        loop {
+            assert!((a.centroid_at(s) - qb.centroid_at(s)).abs() < 1e-12);
+            assert!((b.centroid_at(s) - cb.centroid_at(s)).abs() < 1e-12);
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+        assert_eq!(Bezier(vec![2.0]).control_centroid(), 2.0);
+        assert_eq!(Bezier(vec![2.0]).centroid_at(0.5), 2.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_bezier_empty() {
+        Bezier::<f64>(vec![]).hu(0.5);
+    }
+
+    #[test]
+    fn check_volume_morph() {
+        let to_array = |(x, y, z): (f64, f64, f64)| [x, y, z];
+        let a = Cube::new(Lerp(0.0, 1.0), Lerp(0.0, 1.0), Lerp(0.0, 1.0)).into_map(to_array);
+        let b = Cube::new(QuadraticBezier(0.0, 0.8, 1.0), Smoothstep(0.0, 1.0), Lerp(0.0, 1.0))
+            .into_map(to_array);
+        let c = VolumeMorph(a, b);
+        assert!(checku4(&c));
+
+        let x = ((), (), ());
+        for &t in &[[0.2, 0.5, 0.9], [0.7, 0.1, 0.4]] {
+            assert_eq!(c.hu([t[0], t[1], t[2], 0.0]), a.h(x, t));
+            assert_eq!(c.hu([t[0], t[1], t[2], 1.0]), b.h(x, t));
+        }
+        let mid = c.hu([0.5, 0.5, 0.5, 0.5]);
+        assert_eq!(mid, a.h(x, [0.5; 3]).scale(0.5).add_vec(b.h(x, [0.5; 3]).scale(0.5)));
+    }
+
+    #[test]
+    fn check_rational_bezier() {
+        let w = 0.5f64.sqrt();
+        let a = RationalBezier::new(vec![[1.0, 0.0], [1.0, 1.0], [0.0, 1.0]], vec![1.0, w, 1.0]);
+        assert!(checku(&a));
+        let mut s = 0.0;
+        loop {
+            let p = a.hu(s);
+            assert!((p[0].hypot(p[1]) - 1.0).abs() < 1e-12);
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+        let p = a.hu(0.5);
+        assert!((p[0] - w).abs() < 1e-12 && (p[1] - w).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_rational_bezier_mismatched_weights() {
+        RationalBezier::new(vec![0.0, 1.0], vec![1.0]);
+    }
+
+    #[test]
+    fn check_bisector() {
+        let a = Lerp(0.0, 4.0).into_map(|x| [x, 0.0]);
+        let b = Lerp(0.0, 4.0).into_map(|x| [x, 2.0]);
+        let mid = bisector(&a, &b, 4);
+        assert_eq!(mid, vec![[0.0, 1.0], [1.0, 1.0], [2.0, 1.0], [3.0, 1.0], [4.0, 1.0]]);
+    }
+
+    #[test]
+    fn check_catmull_rom() {
+        let points = vec![[0.0, 0.0], [1.0, 2.0], [3.0, 1.0], [4.0, 3.0]];
+        let a = CatmullRom(points.clone());
+        assert!(checku(&a));
+        for (i, p) in points.iter().enumerate() {
+            let q = a.hu(i as f64 / 3.0);
+            assert!((q[0] - p[0]).abs() < 1e-12 && (q[1] - p[1]).abs() < 1e-12);
+        }
+
+        let b = CatmullRom(vec![1.0, 5.0]);
+        let l = Lerp(1.0, 5.0);
+        let mut s = 0.0;
+        loop {
+            assert!((b.hu(s) - l.hu(s)).abs() < 1e-12);
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+        assert_eq!(CatmullRom(vec![2.0]).hu(0.5), 2.0);
+    }
+
+    #[test]
+    fn check_wrap_scalar() {
+        let a = Circle {center: [0.0, 0.0], radius: 1.0};
+        let b = WrapScalar(a);
+        assert!(checku(&b));
+        assert_eq!(b.h((), 1.25), a.h((), 0.25));
+        assert_eq!(b.h((), 7.5), a.h((), 0.5));
+        assert_eq!(b.h((), -0.25), a.h((), 0.75));
+    }
+
+    #[test]
+    fn check_hermite() {
+        let a = Hermite {p0: [0.0, 1.0], m0: [2.0, 0.0], p1: [3.0, -1.0], m1: [0.0, 5.0]};
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.0), [0.0, 1.0]);
+        assert_eq!(a.hu(1.0), [3.0, -1.0]);
+
+        let cb = CubicBezier(0.3, 1.7, -0.8, 0.9);
+        let b = Hermite::from_bezier(cb);
+        assert!((b.m0 - 4.2).abs() < 1e-12);
+        let mut s = 0.0;
+        loop {
+            assert!((b.hu(s) - cb.hu(s)).abs() < 1e-12);
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+    }
+
+    #[test]
+    fn check_glyph_morph() {
+        let thin = vec![vec![[0.0, 0.0], [1.0, 0.0], [1.0, 4.0], [0.0, 4.0]]];
+        let bold = vec![vec![[0.0, 0.0], [3.0, 0.0], [3.0, 4.0], [0.0, 4.0]]];
+        let a = GlyphMorph::new(thin, bold);
+        assert!(checku(&a));
+        let mid = a.hu(0.5);
+        assert_eq!(mid.len(), 1);
+        assert_eq!(mid[0][2], [2.0, 4.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_glyph_morph_mismatched_points() {
+        GlyphMorph::new(vec![vec![[0.0, 0.0]]], vec![vec![[0.0, 0.0], [1.0, 0.0]]]);
+    }
+
+    #[test]
+    fn check_slerp() {
+        let a = Slerp([2.0, 0.0, 0.0], [0.0, 0.0, 1.0]);
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.0), [1.0, 0.0, 0.0]);
+        let mut s = 0.0;
+        loop {
+            let p = a.hu(s);
+            assert!((dot3(p, p) - 1.0).abs() < 1e-12);
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+        let mid = a.hu(0.5);
+        let d = 0.5f64.sqrt();
+        assert!((mid[0] - d).abs() < 1e-12 && mid[1] == 0.0 && (mid[2] - d).abs() < 1e-12);
+
+        let b = Slerp([0.0, 1.0, 0.0], [0.0, 1.0, 1e-9]);
+        let p = b.hu(0.5);
+        assert!((dot3(p, p) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn check_auto_lerp() {
+        let a = AutoLerp(3.0, 10.0);
+        let b = Lerp(3.0, 10.0);
+        assert!(checku(&a));
+        let mut s = 0.0;
+        loop {
+            assert_eq!(a.hu(s), b.hu(s));
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+        assert_eq!(AutoLerp([0.0, 2.0], [4.0, 0.0]).hu(0.5), [2.0, 1.0]);
+
+        #[derive(Clone, Debug, PartialEq)]
+        struct Label(String);
+
+        impl Tween for Label {
+            fn tween(&self, other: &Label, s: f64) -> Label {
+                if s < 0.5 {self.clone()} else {other.clone()}
+            }
+        }
+
+        let c = AutoLerp(Label("start".into()), Label("end".into()));
+        assert!(checku(&c));
+        assert_eq!(c.hu(0.25), Label("start".into()));
+        assert_eq!(c.hu(0.75), Label("end".into()));
+    }
+
+    #[test]
+    fn check_quat_slerp() {
+        use std::f64::consts::PI;
+
+        let id = [0.0, 0.0, 0.0, 1.0];
+        let rot90 = [0.0, 0.0, (PI / 4.0).sin(), (PI / 4.0).cos()];
+        let rot45 = [0.0, 0.0, (PI / 8.0).sin(), (PI / 8.0).cos()];
+        let a = QuatSlerp(id, rot90);
+        assert!(checku(&a));
+        let mut s = 0.0;
+        loop {
+            let q = a.hu(s);
+            assert!((q.iter().map(|v| v * v).sum::<f64>() - 1.0).abs() < 1e-12);
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+        let q = a.hu(0.5);
+        for i in 0..4 {assert!((q[i] - rot45[i]).abs() < 1e-12)}
+
+        // The negated quaternion is the same rotation, so the shortest path is the same.
+        let b = QuatSlerp(id, rot90.scale(-1.0));
+        assert!(checku(&b));
+        let q = b.hu(0.5);
+        for i in 0..4 {assert!((q[i] - rot45[i]).abs() < 1e-12)}
+        assert_eq!(b.g(()), a.g(()));
+        assert_eq!(check_continuity(&b, (), 100, 0.05), Ok(()));
+    }
+
+    #[test]
+    fn check_self_intersections() {
+        let circle = Circle {center: [0.0, 0.0], radius: 1.0};
+        assert!(self_intersections(&circle, 63).is_empty());
+
+        let figure_eight = circle.into_map(|p| [p[0], p[0] * p[1]]);
+        let res = self_intersections(&figure_eight, 63);
+        assert_eq!(res.len(), 1);
+        let (p, s, t) = res[0];
+        // The polyline only approximates the curve.
+        assert!(p[0].abs() < 1e-3 && p[1].abs() < 1e-3);
+        assert!((s - 0.25).abs() < 1e-3 && (t - 0.75).abs() < 1e-3);
+    }
+
+    #[test]
+    fn check_then() {
+        let a = Then {first: Lerp(0.0, 1.0), second: Lerp(1.0, 5.0), split: 0.25};
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.125), 0.5);
+        assert_eq!(a.hu(0.25), 1.0);
+        assert_eq!(a.hu(0.625), 3.0);
+        assert_eq!(a.hu(1.0), 5.0);
+
+        let b = Piecewise::new(vec![0.0, 0.25, 1.0], vec![Lerp(0.0, 1.0), Lerp(1.0, 5.0)]);
+        let mut s = 0.0;
+        loop {
+            assert!((a.hu(s) - b.hu(s)).abs() < 1e-12);
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+    }
+
+    #[test]
+    fn check_delay() {
+        let a = Delay::new(Lerp(2.0, 4.0), 0.5);
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.0), 2.0);
+        assert_eq!(a.hu(0.25), 2.0);
+        assert_eq!(a.hu(0.5), 2.0);
+        assert_eq!(a.hu(0.75), 3.0);
+        assert_eq!(a.hu(1.0), 4.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_delay_one() {
+        let _ = Delay::new(Lerp(2.0, 4.0), 1.0);
+    }
+
+    #[test]
+    fn check_timeline_blend() {
+        let a = Keyframes::new(vec![0.0, 1.0], vec![0.0, 2.0]);
+        let b = Keyframes::new(vec![0.0, 1.0], vec![4.0, 10.0]);
+        let c = TimelineBlend(a, b);
+        assert!(check(&c, 0.5));
+        assert_eq!(c.h(0.5, 0.0), 1.0);
+        assert_eq!(c.h(0.5, 1.0), 7.0);
+        assert_eq!(c.h(0.5, 0.5), 4.0);
+        assert_eq!(c.h(0.0, 0.5), 2.0);
+    }
+
+    #[test]
+    fn check_c1_joints() {
+        let a = Then {first: Lerp(0.0, 1.0), second: Lerp(1.0, 0.0), split: 0.5};
+        assert!(!a.check_c1((), 1e-4));
+        let b = Then {first: Lerp(0.0, 1.0), second: Lerp(1.0, 2.0), split: 0.5};
+        assert!(b.check_c1((), 1e-4));
+        let c = Piecewise::new(vec![0.0, 0.25, 1.0], vec![Lerp(0.0, 1.0), Lerp(1.0, 2.0)]);
+        assert!(!c.check_c1((), 1e-4));
+        let d = Piecewise::new(vec![0.0, 0.25, 1.0], vec![Lerp(0.0, 1.0), Lerp(1.0, 4.0)]);
+        assert!(d.check_c1((), 1e-4));
+    }
+
+    #[test]
+    fn check_keyframes() {
+        let a = Keyframes::new(vec![0.0, 0.25, 1.0], vec![1.0, 3.0, 0.0]);
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.0), 1.0);
+        assert_eq!(a.hu(0.25), 3.0);
+        assert_eq!(a.hu(1.0), 0.0);
+        assert_eq!(a.hu(0.125), 2.0);
+        assert_eq!(a.hu(0.625), 1.5);
+
+        let b = a.with_easings(vec![Easing::EaseIn(2), Easing::Linear]);
+        assert_eq!(b.hu(0.125), 1.5);
+        assert_eq!(b.hu(0.25), 3.0);
+        assert_eq!(b.hu(0.625), 1.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_keyframes_unsorted() {
+        Keyframes::new(vec![0.0, 0.75, 0.5, 1.0], vec![0.0; 4]);
+    }
+
+    #[test]
+    fn check_dtw_morph() {
+        // An L-shaped stroke, sampled with different vertex counts.
+        let sample = |n: u32| -> Vec<[f64; 2]> {
+            (0..n + 1).map(|i| {
+                let t = 2.0 * i as f64 / n as f64;
+                if t <= 1.0 {[t, 0.0]} else {[1.0, t - 1.0]}
+            }).collect()
+        };
+        let a = DtwMorph::new(sample(8), sample(14));
+        assert!(check(&a, 0.3));
+        assert_eq!(a.alignment()[0], (0, 0));
+        assert_eq!(a.hu(0.0), [0.0, 0.0]);
+        assert_eq!(a.h(1.0, 0.5), [1.0, 1.0]);
+        let mut u = 0.0;
+        loop {
+            let (p, q) = (a.f(u), a.g(u));
+            assert!((p[0] - q[0]).hypot(p[1] - q[1]) < 0.15);
+            u += 0.05;
+            if u > 1.0 {break}
+        }
+    }
+
+    #[test]
+    fn check_time_warp() {
+        let a = Lerp(0.0, 2.0);
+        let b = a.time_warp(|s: f64| s * s * (3.0 - 2.0 * s));
+        assert!(checku(&b));
+        assert_eq!(b.hu(0.0), 0.0);
+        assert_eq!(b.hu(0.5), 1.0);
+        assert_eq!(b.hu(1.0), 2.0);
+        assert!(b.hu(0.25) < a.hu(0.25));
+        assert!(b.hu(0.75) > a.hu(0.75));
+        let c = Lerp(0.0, 2.0).into_time_warp(|s: f64| s.sqrt());
+        assert_eq!(c.hu(0.25), 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_time_warp_invalid() {
+        let a = Lerp(0.0, 2.0).into_time_warp(|s: f64| 0.5 * s);
+        a.hu(0.5);
+    }
+
+    #[test]
+    fn check_symmetrize() {
+        let a = Symmetrize(AutoLerp([1.0, 0.1], [0.2, 0.6]), 4);
+        let mut quadrants = [false; 4];
+        let mut t = 0.0;
+        loop {
+            let r = a.hu(t / 4.0);
+            for k in 0..4 {
+                let p = a.hu((k as f64 + t) / 4.0);
+                let i = match (p[0] > 0.0, p[1] > 0.0) {
+                    (true, true) => 0,
+                    (false, true) => 1,
+                    (false, false) => 2,
+                    (true, false) => 3,
+                };
+                quadrants[i] = true;
+                assert!((p[0].hypot(p[1]) - r[0].hypot(r[1])).abs() < 1e-12);
+            }
+            t += 0.1;
+            if t > 0.95 {break}
+        }
+        assert_eq!(quadrants, [true; 4]);
+        assert_eq!(a.hu(0.0), [1.0, 0.1]);
+    }
+
+    #[test]
+    fn check_ping_pong() {
+        let a = Lerp(1.0, 3.0);
+        let b = a.ping_pong();
+        assert!(checku(&b));
+        assert_eq!(b.f(()), 1.0);
+        assert_eq!(b.g(()), 1.0);
+        assert_eq!(b.hu(0.0), b.hu(1.0));
+        assert_eq!(b.hu(0.5), 3.0);
+        assert_eq!(b.hu(0.25), 2.0);
+        assert_eq!(b.hu(0.75), 2.0);
+        let c = Lerp(1.0, 3.0).into_ping_pong();
+        assert_eq!(c.hu(1.0), 1.0);
+    }
+
+    #[test]
+    fn check_unit() {
+        assert_eq!(Unit::new(1.5), None);
+        assert_eq!(Unit::new(-0.1), None);
+        assert_eq!(Unit::new(f64::NAN), None);
+        let s = Unit::new(0.25).unwrap();
+        assert_eq!(s.get(), 0.25);
+        let a = Lerp(0.0, 4.0);
+        assert_eq!(a.h_unit((), s), 1.0);
+        assert_eq!(a.h_unit((), Unit::new(1.0).unwrap()), 4.0);
+    }
+
+    #[test]
+    fn check_blend() {
+        let a = Lerp(0.0, 4.0);
+        let b = Lerp(2.0, 10.0);
+        let c = a.blend(b, 0.25);
+        assert!(checku(&c));
+        assert_eq!(c.f(()), 0.5);
+        assert_eq!(c.g(()), 5.5);
+        assert_eq!(a.blend(b, 0.0).hu(0.5), a.hu(0.5));
+        assert_eq!(a.blend(b, 1.0).hu(0.5), b.hu(0.5));
+        let mut w = 0.0;
+        loop {
+            let y = a.blend(b, w).hu(0.5);
+            assert!((y - (2.0 + 4.0 * w)).abs() < 1e-12);
+            w += 0.1;
+            if w > 1.0 {break}
+        }
+        let d = Lerp(0.0, 4.0).into_blend(b, 0.5);
+        assert_eq!(d.hu(0.5), 4.0);
+    }
+
+    #[test]
+    fn check_chain_blend() {
+        let dirac = DiracFrom::new(|x: f64| x - 2.0, |x: f64| x + 2.0);
+        let a = Compose::<_, _, f64, f64>::new(Lerp(3.0, 10.0), dirac).into_diagonal();
+        let b = Lerp(0.0, 12.0);
+        let c = ChainBlend(&a, b);
+        assert!(checku(&c));
+        assert_eq!(c.f(()), 1.0);
+        assert_eq!(c.g(()), 12.0);
+        assert_eq!(c.hu(0.0), 1.0);
+        assert_eq!(c.hu(0.5), 0.5 * a.hu(0.5) + 0.5 * b.hu(0.5));
+        assert_eq!(c.hu(0.5), 7.25);
+        assert_eq!(c.hu(1.0), 12.0);
+    }
+
+    #[test]
+    fn check_approx_rounding() {
+        use std::f64::consts::FRAC_PI_2;
+
+        // A quarter turn where `h` is computed with trigonometry,
+        // which does not give exactly `g` at the end.
+        struct QuarterTurn;
+
+        impl Homotopy<()> for QuarterTurn {
+            type Y = [f64; 2];
+
+            fn f(&self, _: ()) -> [f64; 2] {[1.0, 0.0]}
+            fn g(&self, _: ()) -> [f64; 2] {[0.0, 1.0]}
+            fn h(&self, _: (), s: f64) -> [f64; 2] {
+                let (sin, cos) = (s * FRAC_PI_2).sin_cos();
+                [cos, sin]
+            }
+        }
+
+        assert!(!check(&QuarterTurn, ()));
+        assert!(check_approx(&QuarterTurn, (), 1e-12));
+        assert!(!check_approx(&QuarterTurn, (), 1e-20));
+
+        let a = Compose::<_, _, f64, f64>::new(QuarterTurn, Translate([0.0, 1.0]));
+        assert!(!check2(&a, ()));
+        assert!(check2_approx(&a, (), 1e-12));
+        assert!(check3_approx(&Cube::new(Lerp(0.0, 1.0), Lerp(1.0, 2.0), Lerp(2.0, 3.0))
+            .into_map(|(a, b, c)| [a, b, c]), ((), (), ()), 1e-12));
+    }
+
+    #[test]
+    fn check_radial_gradient_morph() {
+        let white = [1.0, 1.0, 1.0];
+        let black = [0.0, 0.0, 0.0];
+        let red = [1.0, 0.0, 0.0];
+        let a = RadialGradientMorph {
+            from: ([0.0, 0.0], 1.0, [white, black]),
+            to: ([2.0, 0.0], 2.0, [red, white]),
+        };
+        assert!(check(&a, [0.5, 0.5]));
+        assert_eq!(a.gradient_at(0.0), a.from);
+        assert_eq!(a.gradient_at(1.0), a.to);
+        assert_eq!(a.color_at([0.0, 0.0], 0.0), white);
+        assert_eq!(a.color_at([2.0, 0.0], 1.0), red);
+        assert_eq!(a.color_at([0.5, 0.0], 0.0), [0.5; 3]);
+        assert_eq!(a.color_at([5.0, 0.0], 0.0), black);
+        assert_eq!(a.color_at([1.0, 0.0], 0.5), [1.0, 0.5, 0.5]);
+        assert_eq!(a.gradient_at(0.5).1, 1.5);
+    }
+
+    #[test]
+    fn check_continuity_sampling() {
+        let a = Lerp(0.0, 2.0);
+        assert_eq!(check_continuity(&a, (), 100, 0.05), Ok(()));
+        assert_eq!(check_continuity(&a, (), 10, 0.05), Err(0.1));
+
+        let b = DiracFrom::new(|x: f64| x - 2.0, |x: f64| x + 2.0);
+        assert!(check(&b, 0.0));
+        assert_eq!(check_continuity(&b, 0.0, 100, 0.05), Err(0.01));
+    }
+
+    #[test]
+    fn check_quat_keyframes() {
+        let half = 0.5f64.sqrt();
+        let keys = vec![
+            (0.0, [0.0, 0.0, 0.0, 1.0]),
+            (0.4, [0.0, 0.0, half, half]),
+            (1.0, [0.0, 0.0, 2.0, 0.0]),
+        ];
+        let a = QuatKeyframes::new(keys);
+        assert!(checku(&a));
+        assert_eq!(a.hu(0.0), [0.0, 0.0, 0.0, 1.0]);
+        let b = a.hu(0.4);
+        assert_eq!([b[0], b[1]], [0.0, 0.0]);
+        assert!((b[2] - half).abs() < 1e-15 && (b[3] - half).abs() < 1e-15);
+        assert_eq!(a.hu(1.0), [0.0, 0.0, 1.0, 0.0]);
+        let mut s = 0.0;
+        loop {
+            let q = a.hu(s);
+            let len = (q[0] * q[0] + q[1] * q[1] + q[2] * q[2] + q[3] * q[3]).sqrt();
+            assert!((len - 1.0).abs() < 1e-12);
+            s += 0.05;
+            if s > 1.0 {break}
+        }
+    }
+
+    #[test]
+    fn check_quat_keyframes_flipped() {
+        let half = 0.5f64.sqrt();
+        let keys = vec![
+            (0.0, [0.0, 0.0, 0.0, 1.0]),
+            (0.5, [0.0, 0.0, -half, -half]),
+            (1.0, [0.0, 0.0, 1.0, 0.0]),
+        ];
+        let a = QuatKeyframes::new(keys);
+        assert!(checku(&a));
+        let b = a.hu(0.5);
+        assert!((b[2] - half).abs() < 1e-15 && (b[3] - half).abs() < 1e-15);
+        assert_eq!(check_continuity(&a, (), 100, 0.05), Ok(()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_quat_keyframes_unsorted() {
+        let _ = QuatKeyframes::new(vec![
+            (0.0, [0.0, 0.0, 0.0, 1.0]),
+            (0.6, [0.0, 0.0, 1.0, 0.0]),
+            (0.3, [0.0, 1.0, 0.0, 0.0]),
+            (1.0, [1.0, 0.0, 0.0, 0.0]),
+        ]);
+    }
+
+    #[test]
+    fn check_nd_compose() {
+        let a = Compose::<_, _, f64, f64>::new(Lerp(0.0, 1.0), Translate(1.0));
+        let b = Compose::<_, _, [f64; 2], f64>::new(a, Translate(2.0));
+        let c = Compose::<_, _, [f64; 3], f64>::new(b, Translate(3.0));
+        assert_eq!(c.hu([1.0; 4]), 7.0);
+        assert!(check4(&c, ()));
+        assert!(check_nd(&c, (), 4));
+        assert!(check_nd(&b, (), 3));
+
+        let d = Compose::<_, _, [f64; 4], f64>::new(c, Translate(4.0));
+        assert_eq!(d.hu([1.0; 5]), 11.0);
+        assert!(check_nd(&d, (), 5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_nd_wrong_dimension() {
+        let a = Cube4::new(Lerp(1.0, 2.0), Lerp(3.0, 4.0), Lerp(5.0, 6.0), Lerp(7.0, 8.0));
+        let _ = check_nd(&a, ((), (), (), ()), 3);
+    }
+
+    #[test]
+    fn check_sample_with_tangent() {
+        let a = Circle {center: [0.0, 0.0], radius: 2.0};
+        let samples = sample_with_tangent(&a, (), 16);
+        assert_eq!(samples.len(), 17);
+        for (i, &(p, v)) in samples.iter().enumerate() {
+            let s = i as f64 / 16.0;
+            assert_eq!(p, a.hu(s));
+            let w = tangent(&a, (), s, 1.0 / 16.0);
+            assert!((v[0] - w[0]).abs() < 1e-9 && (v[1] - w[1]).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn check_tesseract() {
+        let a = Tesseract::new(Lerp(1.0, 2.0), Lerp(3.0, 4.0), Lerp(5.0, 6.0), Lerp(7.0, 8.0));
+        assert!(check4(&a, ((), (), (), ())));
+        assert_eq!(a.hu([0.5, 0.0, 1.0, 0.5]), (1.5, 3.0, 6.0, 7.5));
+    }
+
+    #[test]
+    fn check_extrude() {
+        let spine = AutoLerp([0.0, 0.0], [4.0, 0.0]);
+        let a = extrude(&spine, [0.0, 3.0], 2.0);
+        assert!(checku2(&a));
+        assert_eq!(a.hu([0.0, 0.0]), [0.0, -1.0]);
+        assert_eq!(a.hu([1.0, 1.0]), [4.0, 1.0]);
+        assert_eq!(a.hu([0.0, 1.0]), [0.0, 1.0]);
+        assert_eq!(a.hu([1.0, 0.0]), [4.0, -1.0]);
+        assert_eq!(a.hu([0.5, 0.5]), [2.0, 0.0]);
+        let mut s = 0.0;
+        loop {
+            let (p, q) = (a.hu([s, 0.0]), a.hu([s, 1.0]));
+            assert_eq!(p[0], q[0]);
+            assert_eq!(q[1] - p[1], 2.0);
+            s += 0.1;
+            if s > 1.0 {break}
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_repeat_mirror_zero() {
+        let a = Lerp(0.0, 1.0).into_map(|t| [t, 0.5]);
+        RepeatMirror(a, 0).hu(0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_checker_zero() {
+        let a = Square::new(Lerp(0.0, 1.0), Lerp(0.0, 1.0));
+        let b = Square::new(Lerp(10.0, 11.0), Lerp(10.0, 11.0));
+        Checker(a, b, 0).hu([0.5, 0.5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_symmetrize_zero() {
+        Symmetrize(AutoLerp([1.0, 0.1], [0.2, 0.6]), 0).hu(0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_gradient_morph_empty() {
+        let _ = GradientMorph::new(vec![], vec![(0.0, [1.0; 3])]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_gradient_morph_unsorted() {
+        let _ = GradientMorph::new(vec![(1.0, [1.0; 3]), (0.0, [0.0; 3])], vec![(0.0, [1.0; 3])]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_slice_z_low_resolution() {
+        let a = Cube::new(Lerp(-1.0, 1.0), Lerp(-1.0, 1.0), Lerp(-1.0, 1.0));
+        let field = a.into_map(|(x, y, z): (f64, f64, f64)| x + y + z);
+        let _ = slice_z(&field, 0.25, [1, 4]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_bisector_zero() {
+        let a = Lerp(0.0, 4.0).into_map(|x| [x, 0.0]);
+        let _ = bisector(&a, &a, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_sample_with_tangent_zero() {
+        let a = Circle {center: [0.0, 0.0], radius: 2.0};
+        let _ = sample_with_tangent(&a, (), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_extrude_zero_direction() {
+        let spine = AutoLerp([0.0, 0.0], [4.0, 0.0]);
+        let _ = extrude(&spine, [0.0, 0.0], 2.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_polar_mesh_no_angular() {
+        let circle = Circle {center: [0.0, 0.0], radius: 1.0};
+        let scale = |(r, p): (f64, [f64; 2])| [r * p[0], r * p[1], 0.0];
+        let disc = Square::new(Lerp(0.0, 1.0), circle).into_map(scale);
+        let _ = polar_mesh(&disc, 4, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn check_polar_mesh_no_radial() {
+        let circle = Circle {center: [0.0, 0.0], radius: 1.0};
+        let scale = |(r, p): (f64, [f64; 2])| [r * p[0], r * p[1], 0.0];
+        let disc = Square::new(Lerp(0.0, 1.0), circle).into_map(scale);
+        let _ = polar_mesh(&disc, 0, 8);
+    }
+
+    #[test]
+    fn check_easing_zero_exponent() {
+        for &e in &[Easing::EaseIn(0), Easing::EaseOut(0), Easing::EaseInOut(0)] {
+            assert_eq!(e.apply(0.0), 0.0);
+            assert_eq!(e.apply(1.0), 1.0);
+            assert_eq!(e.apply(0.25), 0.25);
+        }
+    }
+
+    #[test]
+    fn check_circle_as_rational_beziers() {
+        let a = Circle {center: [1.0, -2.0], radius: 3.0};
+        let arcs = a.as_rational_beziers();
+        assert_eq!(arcs.len(), 4);
+        for (i, arc) in arcs.iter().enumerate() {
+            let i = i as f64;
+            assert!(arc.f(()).approx_eq(&a.hu(0.25 * i), 1e-12));
+            assert!(arc.g(()).approx_eq(&a.hu(0.25 * (i + 1.0)), 1e-12));
+            assert!(arc.hu(0.5).approx_eq(&a.hu(0.25 * i + 0.125), 1e-12));
+            let mut s = 0.0;
+            loop {
+                let p = arc.hu(s);
+                let r = ((p[0] - 1.0).powi(2) + (p[1] + 2.0).powi(2)).sqrt();
+                assert!((r - 3.0).abs() < 1e-12);
+                s += 0.1;
+                if s > 1.0 {break}
+            }
+        }
+    }
 }
@@ -0,0 +1,130 @@
+use super::*;
+
+/// Triangulates a disc or annulus parametrized by `[radius, angle]`.
+///
+/// The first scalar moves outwards and the second scalar goes once around,
+/// such that angle `1.0` coincides with angle `0.0`.
+/// There are `radial` rings and `angular` vertices per ring.
+///
+/// When all points at radius `0.0` coincide, as for a disc,
+/// the innermost ring is replaced by a single center vertex with a triangle fan around it.
+/// This avoids the zero-area triangles a rectangular grid produces at the pole.
+/// Panics if there are less than 1 ring or less than 3 vertices per ring.
+pub fn polar_mesh<H, X>(h: &H, radial: u32, angular: u32) -> (Vec<[f64; 3]>, Vec<[u32; 3]>)
+    where H: Homotopy<X, [f64; 2], Y = [f64; 3]>, X: Default
+{
+    assert!(radial >= 1 && angular >= 3, "Expected at least 1 ring and 3 vertices per ring");
+    let ring = |r: f64| -> Vec<[f64; 3]> {
+        (0..angular).map(|j| h.hu([r, j as f64 / angular as f64])).collect()
+    };
+
+    let mut vertices = vec![];
+    let mut triangles = vec![];
+    let inner = ring(0.0);
+    let fan = inner.iter().all(|p| *p == inner[0]);
+    if fan {
+        vertices.push(inner[0]);
+    } else {
+        vertices.extend(inner);
+    }
+    for i in 1..radial + 1 {
+        vertices.extend(ring(i as f64 / radial as f64));
+    }
+
+    // Index of vertex `j` on ring `i`, where ring 0 is the center when using a fan.
+    let index = |i: u32, j: u32| -> u32 {
+        if fan {1 + (i - 1) * angular + j % angular}
+        else {i * angular + j % angular}
+    };
+    for j in 0..angular {
+        if fan {
+            triangles.push([0, index(1, j), index(1, j + 1)]);
+        } else {
+            triangles.push([index(0, j), index(1, j), index(1, j + 1)]);
+            triangles.push([index(0, j), index(1, j + 1), index(0, j + 1)]);
+        }
+    }
+    for i in 1..radial {
+        for j in 0..angular {
+            triangles.push([index(i, j), index(i + 1, j), index(i + 1, j + 1)]);
+            triangles.push([index(i, j), index(i + 1, j + 1), index(i, j + 1)]);
+        }
+    }
+    (vertices, triangles)
+}
+
+/// Samples a 3D scalar field on the xy-plane at a fixed `z`.
+///
+/// The field is a homotopy over `[x, y, z]` scalars, e.g. built from a `Cube`.
+/// The plane is sampled on a `res[0] × res[1]` grid including the edges,
+/// returned in row-major order with one row per y-value.
+/// Panics if the resolution is less than 2 along any axis.
+pub fn slice_z<H, X>(h: &H, z: f64, res: [u32; 2]) -> Vec<f64>
+    where H: Homotopy<X, [f64; 3], Y = f64>, X: Default
+{
+    assert!(res[0] >= 2 && res[1] >= 2, "Expected at least 2 samples per axis");
+    let mut values = Vec::with_capacity((res[0] * res[1]) as usize);
+    for j in 0..res[1] {
+        let y = j as f64 / (res[1] - 1) as f64;
+        for i in 0..res[0] {
+            let x = i as f64 / (res[0] - 1) as f64;
+            values.push(h.hu([x, y, z]));
+        }
+    }
+    values
+}
+
+/// Sweeps a circle along a 3D spine, producing an open tube mesh.
+///
+/// There are `longitudinal` segments along the spine and `radial` vertices around each ring.
+/// The rings are oriented by a parallel transport frame, which is stable along straight parts
+/// of the spine where the Frenet frame is undefined.
+pub fn tube_mesh<H>(
+    spine: &H,
+    radius: f64,
+    longitudinal: u32,
+    radial: u32,
+) -> (Vec<[f64; 3]>, Vec<[u32; 3]>)
+    where H: Homotopy<(), Y = [f64; 3]>
+{
+    // Picks a unit normal to `t`, starting from the axis least aligned with it.
+    let stable_normal = |t: [f64; 3]| -> [f64; 3] {
+        let a = if t[0].abs() <= t[1].abs() && t[0].abs() <= t[2].abs() {[1.0, 0.0, 0.0]}
+            else if t[1].abs() <= t[2].abs() {[0.0, 1.0, 0.0]}
+            else {[0.0, 0.0, 1.0]};
+        normalize3(a.sub_vec(t.scale(dot3(a, t))))
+    };
+
+    let eps = 0.5 / longitudinal as f64;
+    let mut vertices = Vec::with_capacity(((longitudinal + 1) * radial) as usize);
+    let mut normal: Option<[f64; 3]> = None;
+    for i in 0..longitudinal + 1 {
+        let s = i as f64 / longitudinal as f64;
+        let p = spine.hu(s);
+        let t = normalize3(tangent(spine, (), s, eps));
+        let n = match normal {
+            None => stable_normal(t),
+            Some(n) => {
+                let n = n.sub_vec(t.scale(dot3(n, t)));
+                if dot3(n, n) < 1e-12 {stable_normal(t)} else {normalize3(n)}
+            }
+        };
+        normal = Some(n);
+        let b = cross3(t, n);
+        for j in 0..radial {
+            let angle = j as f64 / radial as f64 * 2.0 * ::std::f64::consts::PI;
+            let offset = n.scale(radius * angle.cos()).add_vec(b.scale(radius * angle.sin()));
+            vertices.push(p.add_vec(offset));
+        }
+    }
+
+    let index = |i: u32, j: u32| i * radial + j % radial;
+    let mut triangles = Vec::with_capacity((longitudinal * radial * 2) as usize);
+    for i in 0..longitudinal {
+        for j in 0..radial {
+            triangles.push([index(i, j), index(i + 1, j), index(i + 1, j + 1)]);
+            triangles.push([index(i, j), index(i + 1, j + 1), index(i, j + 1)]);
+        }
+    }
+    (vertices, triangles)
+}
@@ -0,0 +1,721 @@
+use super::*;
+
+/// Samples a color gradient given as sorted `(position, color)` stops.
+fn gradient_color(stops: &[(f64, [f64; 3])], pos: f64) -> [f64; 3] {
+    let last = stops.len() - 1;
+    if pos <= stops[0].0 {return stops[0].1};
+    if pos >= stops[last].0 {return stops[last].1};
+    let i = stops.iter().position(|stop| stop.0 > pos).unwrap();
+    let (p0, c0) = stops[i - 1];
+    let (p1, c1) = stops[i];
+    let t = (pos - p0) / (p1 - p0);
+    c0.scale(1.0 - t).add_vec(c1.scale(t))
+}
+
+/// Morphs between two color gradients.
+///
+/// Each gradient is a list of `(position, color)` stops sorted by position.
+/// The input is the position along the gradient and the scalar blends between the gradients.
+/// Gradients with different stop counts are resampled onto the union of their stop positions.
+/// Use `new` to check the stops, since evaluating a gradient without stops panics.
+#[derive(Clone)]
+pub struct GradientMorph(pub Vec<(f64, [f64; 3])>, pub Vec<(f64, [f64; 3])>);
+
+impl GradientMorph {
+    /// Creates a new gradient morph.
+    ///
+    /// Panics if any gradient has no stops, or if the stops are not sorted by position.
+    pub fn new(a: Vec<(f64, [f64; 3])>, b: Vec<(f64, [f64; 3])>) -> GradientMorph {
+        assert!(!a.is_empty() && !b.is_empty(), "Expected at least one stop per gradient");
+        assert!(a.windows(2).chain(b.windows(2)).all(|w| w[0].0 <= w[1].0),
+                "Expected stops sorted by position");
+        GradientMorph(a, b)
+    }
+
+    /// Returns the color at position `pos` for blend `s`.
+    pub fn color_at(&self, pos: f64, s: f64) -> [f64; 3] {self.h(pos, s)}
+
+    /// Returns the blended gradient at `s`, with stops at the union of both stop positions.
+    pub fn stops_at(&self, s: f64) -> Vec<(f64, [f64; 3])> {
+        let mut positions: Vec<f64> = self.0.iter().chain(self.1.iter())
+            .map(|stop| stop.0).collect();
+        positions.sort_by(|a, b| a.total_cmp(b));
+        positions.dedup();
+        positions.into_iter().map(|pos| (pos, self.color_at(pos, s))).collect()
+    }
+}
+
+impl Homotopy<f64> for GradientMorph {
+    type Y = [f64; 3];
+
+    fn f(&self, pos: f64) -> Self::Y {gradient_color(&self.0, pos)}
+    fn g(&self, pos: f64) -> Self::Y {gradient_color(&self.1, pos)}
+    fn h(&self, pos: f64, s: f64) -> Self::Y {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.f(pos)} else if s == 1.0 {return self.g(pos)};
+        self.f(pos).scale(1.0 - s).add_vec(self.g(pos).scale(s))
+    }
+}
+
+/// Samples a radial gradient given as `(center, radius, [inner, outer])` at a point.
+fn radial_color(gradient: &([f64; 2], f64, [[f64; 3]; 2]), p: [f64; 2]) -> [f64; 3] {
+    let (center, radius, [inner, outer]) = *gradient;
+    let t = if radius > 0.0 {
+        ((p[0] - center[0]).hypot(p[1] - center[1]) / radius).min(1.0)
+    } else {1.0};
+    inner.scale(1.0 - t).add_vec(outer.scale(t))
+}
+
+/// Morphs between two radial gradients, e.g. for animated spotlight or vignette effects.
+///
+/// Each gradient is given as `(center, radius, [inner, outer])`,
+/// where the color ramps from the inner stop at the center to the outer stop at the radius,
+/// and stays at the outer stop beyond it.
+/// The input is a point and the scalar interpolates the center, radius and colors.
+#[derive(Copy, Clone)]
+pub struct RadialGradientMorph {
+    /// The gradient at the start.
+    pub from: ([f64; 2], f64, [[f64; 3]; 2]),
+    /// The gradient at the end.
+    pub to: ([f64; 2], f64, [[f64; 3]; 2]),
+}
+
+impl RadialGradientMorph {
+    /// Returns the color at `point` for blend `s`.
+    pub fn color_at(&self, point: [f64; 2], s: f64) -> [f64; 3] {self.h(point, s)}
+
+    /// Returns the interpolated gradient at `s`.
+    pub fn gradient_at(&self, s: f64) -> ([f64; 2], f64, [[f64; 3]; 2]) {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.from} else if s == 1.0 {return self.to};
+        let (c0, r0, [i0, o0]) = self.from;
+        let (c1, r1, [i1, o1]) = self.to;
+        let lerp = |a: [f64; 3], b: [f64; 3]| a.scale(1.0 - s).add_vec(b.scale(s));
+        (
+            c0.scale(1.0 - s).add_vec(c1.scale(s)),
+            r0 * (1.0 - s) + r1 * s,
+            [lerp(i0, i1), lerp(o0, o1)],
+        )
+    }
+}
+
+impl Homotopy<[f64; 2]> for RadialGradientMorph {
+    type Y = [f64; 3];
+
+    fn f(&self, p: [f64; 2]) -> Self::Y {radial_color(&self.from, p)}
+    fn g(&self, p: [f64; 2]) -> Self::Y {radial_color(&self.to, p)}
+    fn h(&self, p: [f64; 2], s: f64) -> Self::Y {radial_color(&self.gradient_at(s), p)}
+}
+
+/// An attack-decay-sustain-release envelope.
+#[derive(Copy, Clone)]
+pub struct Adsr {
+    /// Time to rise from zero to full amplitude.
+    pub attack: f64,
+    /// Time to fall from full amplitude to the sustain level.
+    pub decay: f64,
+    /// Amplitude held after the decay, until the note is released.
+    pub sustain: f64,
+    /// Time to fall from the sustain level to zero after release.
+    pub release: f64,
+}
+
+impl Adsr {
+    /// Returns the amplitude at time `t` of a note that is held.
+    pub fn amplitude(&self, t: f64) -> f64 {
+        if t <= 0.0 {0.0}
+        else if t < self.attack {t / self.attack}
+        else if t < self.attack + self.decay {
+            1.0 - (1.0 - self.sustain) * (t - self.attack) / self.decay
+        }
+        else {self.sustain}
+    }
+
+    /// Returns the amplitude at time `t` after a note is released from the sustain level.
+    pub fn release_amplitude(&self, t: f64) -> f64 {
+        if t <= 0.0 {self.sustain}
+        else if t < self.release {self.sustain * (1.0 - t / self.release)}
+        else {0.0}
+    }
+}
+
+/// Morphs between two envelopes by interpolating their parameters.
+#[derive(Copy, Clone)]
+pub struct AdsrMorph(pub Adsr, pub Adsr);
+
+impl AdsrMorph {
+    /// Returns the amplitude at time `t` of a held note, using the envelope blended by `s`.
+    pub fn amplitude_at(&self, t: f64, s: f64) -> f64 {self.hu(s).amplitude(t)}
+}
+
+impl Homotopy<()> for AdsrMorph {
+    type Y = Adsr;
+
+    fn f(&self, _: ()) -> Adsr {self.0}
+    fn g(&self, _: ()) -> Adsr {self.1}
+    fn h(&self, _: (), s: f64) -> Adsr {
+        let lerp = |a: f64, b: f64| Lerp(a, b).hu(s);
+        Adsr {
+            attack: lerp(self.0.attack, self.1.attack),
+            decay: lerp(self.0.decay, self.1.decay),
+            sustain: lerp(self.0.sustain, self.1.sustain),
+            release: lerp(self.0.release, self.1.release),
+        }
+    }
+}
+
+/// Samples a table of evenly spaced values over `[0, 1]`, with linear interpolation.
+pub(crate) fn sample_table(table: &[f64], x: f64) -> f64 {
+    if table.len() == 1 {return table[0]};
+    let last = table.len() - 1;
+    let pos = x.clamp(0.0, 1.0) * last as f64;
+    let i = (pos.floor() as usize).min(last - 1);
+    let t = pos - i as f64;
+    table[i] * (1.0 - t) + table[i + 1] * t
+}
+
+/// Interpolates between two functions precomputed into lookup tables.
+///
+/// This is useful when `f` and `g` are expensive to evaluate.
+/// Each table holds evenly spaced samples over `[0, 1]`.
+/// The input indexes into both tables with linear interpolation and the scalar blends them.
+#[derive(Clone)]
+pub struct TableLerp {
+    /// Samples of the function being mapped from.
+    pub f_table: Vec<f64>,
+    /// Samples of the function being mapped to.
+    pub g_table: Vec<f64>,
+}
+
+impl TableLerp {
+    /// Creates a new table interpolation.
+    ///
+    /// Panics if the tables are empty or have different lengths.
+    pub fn new(f_table: Vec<f64>, g_table: Vec<f64>) -> TableLerp {
+        assert!(!f_table.is_empty(), "Expected non-empty tables");
+        assert_eq!(f_table.len(), g_table.len(), "Expected tables of equal length");
+        TableLerp {f_table, g_table}
+    }
+}
+
+impl Homotopy<f64> for TableLerp {
+    type Y = f64;
+
+    fn f(&self, x: f64) -> f64 {sample_table(&self.f_table, x)}
+    fn g(&self, x: f64) -> f64 {sample_table(&self.g_table, x)}
+    fn h(&self, x: f64, s: f64) -> f64 {Lerp(self.f(x), self.g(x)).hu(s)}
+}
+
+/// Morphs between two heightmaps of the same size.
+///
+/// The heightmaps are stored in row-major order with `width` cells per row,
+/// and the input is the `[column, row]` of a cell.
+/// With `sharpen` set to `0.0`, this is a plain per-cell linear interpolation.
+/// A positive `sharpen` biases the blend toward whichever map has the higher local gradient,
+/// which preserves ridges that would otherwise be flattened halfway through the morph.
+#[derive(Clone)]
+pub struct HeightmapMorph {
+    /// The heightmap being mapped from.
+    pub a: Vec<f64>,
+    /// The heightmap being mapped to.
+    pub b: Vec<f64>,
+    /// The number of cells per row.
+    pub width: u32,
+    /// How much to bias toward the steeper heightmap, usually in `[0, 1]`.
+    pub sharpen: f64,
+}
+
+impl HeightmapMorph {
+    // Computes the magnitude of the local gradient with central differences, clamped at the edges.
+    fn gradient(&self, map: &[f64], p: [u32; 2]) -> f64 {
+        let width = self.width as usize;
+        let height = map.len() / width;
+        let at = |x: usize, y: usize| map[y * width + x];
+        let (x, y) = (p[0] as usize, p[1] as usize);
+        let (x0, x1) = (x.saturating_sub(1), (x + 1).min(width - 1));
+        let (y0, y1) = (y.saturating_sub(1), (y + 1).min(height - 1));
+        let dx = if x1 > x0 {(at(x1, y) - at(x0, y)) / (x1 - x0) as f64} else {0.0};
+        let dy = if y1 > y0 {(at(x, y1) - at(x, y0)) / (y1 - y0) as f64} else {0.0};
+        (dx * dx + dy * dy).sqrt()
+    }
+}
+
+impl Homotopy<[u32; 2]> for HeightmapMorph {
+    type Y = f64;
+
+    fn f(&self, p: [u32; 2]) -> f64 {self.a[(p[1] * self.width + p[0]) as usize]}
+    fn g(&self, p: [u32; 2]) -> f64 {self.b[(p[1] * self.width + p[0]) as usize]}
+    fn h(&self, p: [u32; 2], s: f64) -> f64 {
+        let mut w = s;
+        if self.sharpen != 0.0 {
+            let ga = self.gradient(&self.a, p);
+            let gb = self.gradient(&self.b, p);
+            if ga + gb > 0.0 {
+                let d = (gb - ga) / (ga + gb);
+                w = (s + self.sharpen * d * s * (1.0 - s)).clamp(0.0, 1.0);
+            }
+        }
+        Lerp(self.f(p), self.g(p)).hu(w)
+    }
+}
+
+/// Resamples a closed polygon to `n` vertices evenly spaced along its perimeter,
+/// starting at the first vertex.
+fn resample_polygon(poly: &[[f64; 2]], n: usize) -> Vec<[f64; 2]> {
+    let len = |a: [f64; 2], b: [f64; 2]| ((b[0] - a[0]).powi(2) + (b[1] - a[1]).powi(2)).sqrt();
+    let edges: Vec<([f64; 2], [f64; 2])> = (0..poly.len())
+        .map(|i| (poly[i], poly[(i + 1) % poly.len()])).collect();
+    let perimeter: f64 = edges.iter().map(|&(a, b)| len(a, b)).sum();
+    let mut res = Vec::with_capacity(n);
+    let mut edge = 0;
+    let mut start = 0.0;
+    for k in 0..n {
+        let d = perimeter * k as f64 / n as f64;
+        while edge + 1 < edges.len() && start + len(edges[edge].0, edges[edge].1) <= d {
+            start += len(edges[edge].0, edges[edge].1);
+            edge += 1;
+        }
+        let (a, b) = edges[edge];
+        let l = len(a, b);
+        let t = if l > 0.0 {(d - start) / l} else {0.0};
+        res.push(a.scale(1.0 - t).add_vec(b.scale(t)));
+    }
+    res
+}
+
+/// Morphs between two closed polygons with possibly different vertex counts.
+///
+/// Both polygons are resampled to the larger vertex count, evenly spaced along their perimeters
+/// starting at their first vertex, and corresponding vertices are interpolated linearly.
+/// This avoids the tangling of interpolating mismatched polygons index by index.
+/// `f` and `g` return the resampled polygons.
+#[derive(Clone)]
+pub struct PolygonMorph(pub Vec<[f64; 2]>, pub Vec<[f64; 2]>);
+
+impl PolygonMorph {
+    fn count(&self) -> usize {self.0.len().max(self.1.len())}
+}
+
+impl Homotopy<()> for PolygonMorph {
+    type Y = Vec<[f64; 2]>;
+
+    fn f(&self, _: ()) -> Self::Y {resample_polygon(&self.0, self.count())}
+    fn g(&self, _: ()) -> Self::Y {resample_polygon(&self.1, self.count())}
+    fn h(&self, _: (), s: f64) -> Self::Y {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.f(())} else if s == 1.0 {return self.g(())};
+        self.f(()).into_iter().zip(self.g(()))
+            .map(|(a, b)| a.scale(1.0 - s).add_vec(b.scale(s)))
+            .collect()
+    }
+}
+
+/// Interpolates between two axis-aligned bounding boxes.
+///
+/// Boxes are given as `(min, max)` corners, which are interpolated independently.
+/// This is useful for animating a view window, e.g. the `aabb` of a plot.
+#[derive(Copy, Clone)]
+pub struct AabbLerp {
+    /// The box being mapped from.
+    pub from: ([f64; 2], [f64; 2]),
+    /// The box being mapped to.
+    pub to: ([f64; 2], [f64; 2]),
+}
+
+impl AabbLerp {
+    /// Returns the box at `s` as `[x, y, width, height]`.
+    pub fn rect_at(&self, s: f64) -> [f64; 4] {
+        let (min, max) = self.hu(s);
+        [min[0], min[1], max[0] - min[0], max[1] - min[1]]
+    }
+}
+
+impl Homotopy<()> for AabbLerp {
+    type Y = ([f64; 2], [f64; 2]);
+
+    fn f(&self, _: ()) -> Self::Y {self.from}
+    fn g(&self, _: ()) -> Self::Y {self.to}
+    fn h(&self, _: (), s: f64) -> Self::Y {
+        let lerp = |a: [f64; 2], b: [f64; 2]| [Lerp(a[0], b[0]).hu(s), Lerp(a[1], b[1]).hu(s)];
+        (lerp(self.from.0, self.to.0), lerp(self.from.1, self.to.1))
+    }
+}
+
+/// Morphs one parametric curve into another.
+///
+/// The input is the curve parameter in `[0, 1]`
+/// and the scalar blends linearly between the two curves at that parameter.
+#[derive(Copy, Clone)]
+pub struct ParamMorph<F, G>(pub F, pub G);
+
+impl<F, G> Homotopy<f64> for ParamMorph<F, G>
+    where F: Fn(f64) -> [f64; 2], G: Fn(f64) -> [f64; 2]
+{
+    type Y = [f64; 2];
+
+    fn f(&self, u: f64) -> [f64; 2] {(self.0)(u)}
+    fn g(&self, u: f64) -> [f64; 2] {(self.1)(u)}
+    fn h(&self, u: f64, s: f64) -> [f64; 2] {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.f(u)} else if s == 1.0 {return self.g(u)};
+        self.f(u).scale(1.0 - s).add_vec(self.g(u).scale(s))
+    }
+}
+
+/// Evaluates the quantile function of a histogram over `[0, 1]` at `t`.
+///
+/// The knots are the normalized cumulative masses at the bin edges.
+/// Mass is uniform within each bin, so the quantile is piecewise linear between knots.
+/// When `right` is `true`, the right limit is taken at jumps over empty bins.
+fn quantile(knots: &[f64], t: f64, right: bool) -> f64 {
+    let n = knots.len() - 1;
+    let k = (0..n).find(|&k| {
+            if right {knots[k + 1] > t} else {knots[k + 1] >= t && knots[k + 1] > knots[k]}
+        })
+        .unwrap_or_else(|| (0..n).rev().find(|&k| knots[k + 1] > knots[k]).unwrap());
+    let u = ((t - knots[k]) / (knots[k + 1] - knots[k])).clamp(0.0, 1.0);
+    (k as f64 + u) / n as f64
+}
+
+/// Computes the normalized cumulative masses at the bin edges of a histogram.
+fn cumulative_knots(hist: &[f64]) -> Vec<f64> {
+    let total: f64 = hist.iter().sum();
+    let mut knots = Vec::with_capacity(hist.len() + 1);
+    let mut acc = 0.0;
+    knots.push(0.0);
+    for &m in hist {
+        acc += m;
+        knots.push(acc / total);
+    }
+    knots
+}
+
+/// Morphs between two 1D histograms along their optimal transport geodesic.
+///
+/// The histograms have bins evenly spaced over `[0, 1]`, with mass uniform within each bin.
+/// Instead of blending the bins, the inverse cumulative distributions are interpolated,
+/// which moves mass along the axis and preserves the shape of the distributions
+/// (1D Wasserstein interpolation).
+/// The total mass is interpolated linearly.
+///
+/// Both histograms must have the same number of bins and non-zero total mass.
+/// The input is the bin index.
+#[derive(Clone)]
+pub struct WassersteinMorph(pub Vec<f64>, pub Vec<f64>);
+
+impl WassersteinMorph {
+    /// Returns all blended histogram bins at `s`.
+    ///
+    /// This is faster than evaluating the homotopy once per bin.
+    pub fn bins_at(&self, s: f64) -> Vec<f64> {
+        assert_eq!(self.0.len(), self.1.len(), "Expected histograms of equal length");
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.0.clone()} else if s == 1.0 {return self.1.clone()};
+        let n = self.0.len();
+        let knots0 = cumulative_knots(&self.0);
+        let knots1 = cumulative_knots(&self.1);
+        let mut ts: Vec<f64> = knots0.iter().chain(knots1.iter()).cloned().collect();
+        ts.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        ts.dedup();
+
+        let total = (1.0 - s) * self.0.iter().sum::<f64>() + s * self.1.iter().sum::<f64>();
+        let q = |t: f64, right: bool| {
+            (1.0 - s) * quantile(&knots0, t, right) + s * quantile(&knots1, t, right)
+        };
+        let mut bins = vec![0.0; n];
+        for w in ts.windows(2) {
+            let mass = (w[1] - w[0]) * total;
+            let a = q(w[0], true);
+            let b = q(w[1], false);
+            if b - a < 1e-15 {
+                bins[((a * n as f64) as usize).min(n - 1)] += mass;
+                continue;
+            }
+            let start = ((a * n as f64) as usize).min(n - 1);
+            let end = ((b * n as f64) as usize).min(n - 1);
+            for (i, bin) in bins.iter_mut().enumerate().take(end + 1).skip(start) {
+                let lo = a.max(i as f64 / n as f64);
+                let hi = b.min((i + 1) as f64 / n as f64);
+                if hi > lo {*bin += mass * (hi - lo) / (b - a)};
+            }
+        }
+        bins
+    }
+}
+
+impl Homotopy<usize> for WassersteinMorph {
+    type Y = f64;
+
+    fn f(&self, i: usize) -> f64 {self.0[i]}
+    fn g(&self, i: usize) -> f64 {self.1[i]}
+    fn h(&self, i: usize, s: f64) -> f64 {self.bins_at(s)[i]}
+}
+
+/// Morphs between two signed distance fields, moving one shape's boundary into the other's.
+///
+/// The fields are homotopies over points with a signed distance as output,
+/// negative inside the shape, such that the shape is the zero level set.
+/// For static shapes, use a `Map` of `Id` with a distance function.
+/// The distances are blended linearly, so the zero level set moves from the first shape
+/// to the second.
+///
+/// A linear blend of two signed distance fields is generally not an exact distance field itself,
+/// only an approximation, which is fine for rendering the zero level set but may be off
+/// for effects that rely on exact distances, such as outlines or sphere tracing step sizes.
+#[derive(Copy, Clone)]
+pub struct SdfMorph<A, B>(pub A, pub B);
+
+impl<A, B> Homotopy<[f64; 2]> for SdfMorph<A, B>
+    where A: Homotopy<[f64; 2], Y = f64>, B: Homotopy<[f64; 2], Y = f64>
+{
+    type Y = f64;
+
+    fn f(&self, p: [f64; 2]) -> f64 {self.0.f(p)}
+    fn g(&self, p: [f64; 2]) -> f64 {self.1.g(p)}
+    fn h(&self, p: [f64; 2], s: f64) -> f64 {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.f(p)} else if s == 1.0 {return self.g(p)};
+        (1.0 - s) * self.0.h(p, s) + s * self.1.h(p, s)
+    }
+}
+
+/// Morphs between two grid deformations, e.g. for image warping.
+///
+/// Each grid holds `res[0] × res[1]` control points in row-major order,
+/// where the control point at column `i` and row `j` is where the UV coordinate
+/// `[i / (res[0] - 1), j / (res[1] - 1)]` is moved to.
+/// The input is a UV coordinate in `[0, 1]²`, which is mapped bilinearly
+/// through the grid blended linearly by the scalar.
+#[derive(Clone)]
+pub struct WarpMorph {
+    /// The control points of the grid being mapped from.
+    pub from: Vec<[f64; 2]>,
+    /// The control points of the grid being mapped to.
+    pub to: Vec<[f64; 2]>,
+    /// The number of columns and rows of control points.
+    pub res: [u32; 2],
+}
+
+impl WarpMorph {
+    /// Creates a new warp morph.
+    ///
+    /// Panics if the resolution is less than 2 along any axis,
+    /// or if the grids do not have `res[0] * res[1]` control points.
+    pub fn new(from: Vec<[f64; 2]>, to: Vec<[f64; 2]>, res: [u32; 2]) -> WarpMorph {
+        assert!(res[0] >= 2 && res[1] >= 2, "Expected at least 2 control points per axis");
+        let n = (res[0] * res[1]) as usize;
+        assert_eq!(from.len(), n, "Expected `from` grid to match the resolution");
+        assert_eq!(to.len(), n, "Expected `to` grid to match the resolution");
+        WarpMorph {from, to, res}
+    }
+
+    fn warp(&self, uv: [f64; 2], s: f64) -> [f64; 2] {
+        let point = |i: u32, j: u32| {
+            let k = (j * self.res[0] + i) as usize;
+            // Handle special cases to get exact values.
+            if s == 0.0 {return self.from[k]} else if s == 1.0 {return self.to[k]};
+            self.from[k].scale(1.0 - s).add_vec(self.to[k].scale(s))
+        };
+        let cell = |t: f64, n: u32| {
+            let pos = t.clamp(0.0, 1.0) * (n - 1) as f64;
+            let i = (pos.floor() as u32).min(n - 2);
+            (i, pos - i as f64)
+        };
+        let (i, u) = cell(uv[0], self.res[0]);
+        let (j, v) = cell(uv[1], self.res[1]);
+        let top = point(i, j).scale(1.0 - u).add_vec(point(i + 1, j).scale(u));
+        let bottom = point(i, j + 1).scale(1.0 - u).add_vec(point(i + 1, j + 1).scale(u));
+        top.scale(1.0 - v).add_vec(bottom.scale(v))
+    }
+}
+
+impl Homotopy<[f64; 2]> for WarpMorph {
+    type Y = [f64; 2];
+
+    fn f(&self, uv: [f64; 2]) -> [f64; 2] {self.warp(uv, 0.0)}
+    fn g(&self, uv: [f64; 2]) -> [f64; 2] {self.warp(uv, 1.0)}
+    fn h(&self, uv: [f64; 2], s: f64) -> [f64; 2] {self.warp(uv, s)}
+}
+
+/// Morphs between two volumes given by 3D homotopies, adding a fourth scalar for the blend.
+///
+/// The first three scalar components are passed to both volumes,
+/// and the fourth component blends linearly from the first volume to the second.
+/// This can drive free-form deformation lattices.
+/// For the 4D homotopy constraints to hold on every side,
+/// the two volumes should share their `f` and `g` endpoints.
+#[derive(Copy, Clone)]
+pub struct VolumeMorph<A, B>(pub A, pub B);
+
+impl<X, A, B> Homotopy<X, [f64; 4]> for VolumeMorph<A, B>
+    where A: Homotopy<X, [f64; 3]>, B: Homotopy<X, [f64; 3], Y = A::Y>, A::Y: Vector, X: Clone
+{
+    type Y = A::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.0.f(x)}
+    fn g(&self, x: X) -> Self::Y {self.1.g(x)}
+    fn h(&self, x: X, s: [f64; 4]) -> Self::Y {
+        let t = [s[0], s[1], s[2]];
+        // Handle special cases to get exact values.
+        if s[3] == 0.0 {return self.0.h(x, t)} else if s[3] == 1.0 {return self.1.h(x, t)};
+        self.0.h(x.clone(), t).scale(1.0 - s[3]).add_vec(self.1.h(x, t).scale(s[3]))
+    }
+}
+
+/// Morphs between two glyph outlines, given as contours of control points.
+///
+/// Contours and points correspond by index: contour `i` of one glyph morphs into contour `i`
+/// of the other, point by point. The glyphs must have the same number of contours,
+/// and matching contours the same number of points, which is usually the case
+/// for masters of a variable font designed to be interpolated.
+#[derive(Clone)]
+pub struct GlyphMorph {
+    /// The contours of the glyph being mapped from.
+    pub from: Vec<Vec<[f64; 2]>>,
+    /// The contours of the glyph being mapped to.
+    pub to: Vec<Vec<[f64; 2]>>,
+}
+
+impl GlyphMorph {
+    /// Creates a new glyph morph.
+    ///
+    /// Panics if the glyphs do not have matching contour and point counts.
+    pub fn new(from: Vec<Vec<[f64; 2]>>, to: Vec<Vec<[f64; 2]>>) -> GlyphMorph {
+        assert_eq!(from.len(), to.len(), "Expected the same number of contours");
+        assert!(from.iter().zip(to.iter()).all(|(a, b)| a.len() == b.len()),
+                "Expected matching contours to have the same number of points");
+        GlyphMorph {from, to}
+    }
+}
+
+impl Homotopy<()> for GlyphMorph {
+    type Y = Vec<Vec<[f64; 2]>>;
+
+    fn f(&self, _: ()) -> Self::Y {self.from.clone()}
+    fn g(&self, _: ()) -> Self::Y {self.to.clone()}
+    fn h(&self, _: (), s: f64) -> Self::Y {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.f(())} else if s == 1.0 {return self.g(())};
+        self.from.iter().zip(self.to.iter()).map(|(a, b)| {
+            a.iter().zip(b.iter()).map(|(&p, &q)| p.scale(1.0 - s).add_vec(q.scale(s))).collect()
+        }).collect()
+    }
+}
+
+/// Computes the dynamic time warping alignment of two non-empty polylines.
+///
+/// Returns the pairs of vertex indices, in order.
+fn dtw_alignment(a: &[[f64; 2]], b: &[[f64; 2]]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    let dist = |i: usize, j: usize| (a[i][0] - b[j][0]).hypot(a[i][1] - b[j][1]);
+    let mut cost = vec![vec![0.0f64; m]; n];
+    for i in 0..n {
+        for j in 0..m {
+            let prev = match (i, j) {
+                (0, 0) => 0.0,
+                (0, _) => cost[0][j - 1],
+                (_, 0) => cost[i - 1][0],
+                _ => cost[i - 1][j].min(cost[i][j - 1]).min(cost[i - 1][j - 1]),
+            };
+            cost[i][j] = dist(i, j) + prev;
+        }
+    }
+
+    let (mut i, mut j) = (n - 1, m - 1);
+    let mut path = vec![(i, j)];
+    while i > 0 || j > 0 {
+        if i == 0 {
+            j -= 1;
+        } else if j == 0 {
+            i -= 1;
+        } else {
+            let diag = cost[i - 1][j - 1];
+            if diag <= cost[i - 1][j] && diag <= cost[i][j - 1] {
+                i -= 1;
+                j -= 1;
+            } else if cost[i - 1][j] <= cost[i][j - 1] {
+                i -= 1;
+            } else {
+                j -= 1;
+            }
+        }
+        path.push((i, j));
+    }
+    path.reverse();
+    path
+}
+
+/// Morphs between two polylines with different vertex counts,
+/// using dynamic time warping (DTW) to find corresponding vertices.
+///
+/// DTW pairs up vertices such that the total distance between paired vertices is minimal,
+/// while keeping both polylines in order. Every vertex is paired at least once,
+/// and the pairs form an alignment from the first vertices to the last vertices.
+/// The input is the normalized arc position along the alignment,
+/// measured by the average length of the steps along both polylines,
+/// and the scalar blends between the paired points.
+///
+/// The alignment takes `O(n m)` time for polylines with `n` and `m` vertices,
+/// so it is computed once by `new`.
+#[derive(Clone)]
+pub struct DtwMorph {
+    alignment: Vec<(usize, usize)>,
+    pairs: Vec<([f64; 2], [f64; 2])>,
+    table: Vec<f64>,
+}
+
+impl DtwMorph {
+    /// Creates a new morph, aligning the two polylines.
+    ///
+    /// Panics if any polyline is empty.
+    pub fn new(a: Vec<[f64; 2]>, b: Vec<[f64; 2]>) -> DtwMorph {
+        assert!(!a.is_empty() && !b.is_empty(), "Expected non-empty polylines");
+        let alignment = dtw_alignment(&a, &b);
+        let pairs: Vec<([f64; 2], [f64; 2])> = alignment.iter().map(|&(i, j)| (a[i], b[j]))
+            .collect();
+        let len = |p: [f64; 2], q: [f64; 2]| (p[0] - q[0]).hypot(p[1] - q[1]);
+        let mut cumulative = vec![0.0];
+        for k in 1..pairs.len() {
+            let ((a0, b0), (a1, b1)) = (pairs[k - 1], pairs[k]);
+            let step = 0.5 * (len(a0, a1) + len(b0, b1));
+            cumulative.push(cumulative[k - 1] + step);
+        }
+        let total = cumulative[pairs.len() - 1];
+        let table = if total > 0.0 {
+            cumulative.iter().map(|c| c / total).collect()
+        } else if pairs.len() > 1 {
+            (0..pairs.len()).map(|k| k as f64 / (pairs.len() - 1) as f64).collect()
+        } else {
+            cumulative
+        };
+        DtwMorph {alignment, pairs, table}
+    }
+
+    /// Returns the pairs of vertex indices in the DTW alignment, in order.
+    pub fn alignment(&self) -> &[(usize, usize)] {&self.alignment}
+
+    /// Returns the pair of corresponding points at normalized arc position `u`.
+    fn pair_at(&self, u: f64) -> ([f64; 2], [f64; 2]) {
+        let (pairs, table) = (&self.pairs, &self.table);
+        if pairs.len() == 1 {return pairs[0]};
+        let u = u.clamp(0.0, 1.0);
+        let k = table[1..].iter().position(|&c| u <= c).unwrap_or(pairs.len() - 2);
+        let seg = table[k + 1] - table[k];
+        let t = if seg > 0.0 {(u - table[k]) / seg} else {0.0};
+        let ((a0, b0), (a1, b1)) = (pairs[k], pairs[k + 1]);
+        (a0.scale(1.0 - t).add_vec(a1.scale(t)), b0.scale(1.0 - t).add_vec(b1.scale(t)))
+    }
+}
+
+impl Homotopy<f64> for DtwMorph {
+    type Y = [f64; 2];
+
+    fn f(&self, u: f64) -> [f64; 2] {self.pair_at(u).0}
+    fn g(&self, u: f64) -> [f64; 2] {self.pair_at(u).1}
+    fn h(&self, u: f64, s: f64) -> [f64; 2] {
+        let (a, b) = self.pair_at(u);
+        // Handle special cases to get exact values.
+        if s == 0.0 {return a} else if s == 1.0 {return b};
+        a.scale(1.0 - s).add_vec(b.scale(s))
+    }
+}
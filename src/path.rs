@@ -0,0 +1,535 @@
+use super::*;
+
+/// Progressively reveals a curve, e.g. for line drawing animations.
+///
+/// At scalar `s`, the output is the polyline traced from `0.0` up to `s`,
+/// using the second field as the number of segments for the whole curve.
+/// At `0.0` this is the single start point, and at `1.0` it is the whole curve.
+#[derive(Copy, Clone)]
+pub struct DrawOn<T>(pub T, pub u32);
+
+impl<X, T> Homotopy<X> for DrawOn<T>
+    where T: Homotopy<X>, X: Clone
+{
+    type Y = Vec<T::Y>;
+
+    fn f(&self, x: X) -> Self::Y {self.h(x, 0.0)}
+    fn g(&self, x: X) -> Self::Y {self.h(x, 1.0)}
+    fn h(&self, x: X, s: f64) -> Self::Y {
+        let n = self.1;
+        let mut res: Vec<T::Y> = (0..n)
+            .map(|i| i as f64 / n as f64)
+            .take_while(|&t| t < s)
+            .map(|t| self.0.h(x.clone(), t))
+            .collect();
+        res.push(self.0.h(x, s));
+        res
+    }
+}
+
+/// Returns the midpoint curve between two 2D curves, sampled at `n + 1` matching scalars.
+///
+/// This is the centerline between two boundaries, e.g. the sides of a road or a glyph stroke.
+/// Since the curves are matched by scalar rather than by distance,
+/// both curves should be parametrized consistently.
+/// Panics if `n` is zero.
+pub fn bisector<A, B, XA, XB>(a: &A, b: &B, n: u32) -> Vec<[f64; 2]>
+    where A: Homotopy<XA, Y = [f64; 2]>, B: Homotopy<XB, Y = [f64; 2]>,
+          XA: Default, XB: Default
+{
+    assert!(n >= 1, "Expected at least one segment");
+    (0..n + 1).map(|i| {
+        let s = i as f64 / n as f64;
+        a.hu(s).add_vec(b.hu(s)).scale(0.5)
+    }).collect()
+}
+
+/// Extrudes a 2D curve into a ribbon, by offsetting it along a direction.
+///
+/// The first scalar component moves along the spine,
+/// and the second moves across the ribbon from `-width/2` to `width/2` along the direction.
+/// The direction is normalized, so only its orientation matters.
+/// Panics if the direction is zero.
+pub fn extrude<'a, H>(
+    spine: &'a H,
+    direction: [f64; 2],
+    width: f64
+) -> impl Homotopy<(), [f64; 2], Y = [f64; 2]> + 'a
+    where H: Homotopy<(), Y = [f64; 2]>
+{
+    let len = direction[0].hypot(direction[1]);
+    assert!(len > 0.0, "Expected a non-zero direction");
+    let d = [direction[0] / len, direction[1] / len];
+    spine.smap(move |p, t| {
+        let offset = width * (t - 0.5);
+        [p[0] + d[0] * offset, p[1] + d[1] * offset]
+    })
+}
+
+/// A stroke of varying width along a 2D spine.
+///
+/// The first field is the spine and the second field gives the half-width at each scalar.
+/// The outline is offset to both sides of the spine along the numeric normal.
+#[derive(Copy, Clone)]
+pub struct VariableWidth<T, W>(pub T, pub W);
+
+impl<T, W> VariableWidth<T, W>
+    where T: Homotopy<(), Y = [f64; 2]>, W: Homotopy<(), Y = f64>
+{
+    /// Returns the closed outline of the stroke, using `n` segments along the spine.
+    ///
+    /// The outline goes along the left side from start to end,
+    /// then back along the right side from end to start.
+    /// The normal is computed from a central difference with a step of half a segment.
+    pub fn outline(&self, n: u32) -> Vec<[f64; 2]> {
+        let eps = 0.5 / n as f64;
+        let mut left = Vec::with_capacity(n as usize + 1);
+        let mut right = Vec::with_capacity(n as usize + 1);
+        for i in 0..n + 1 {
+            let s = i as f64 / n as f64;
+            let p = self.0.hu(s);
+            let t = tangent(&self.0, (), s, eps);
+            let len = (t[0] * t[0] + t[1] * t[1]).sqrt();
+            let w = self.1.hu(s);
+            let d = [-t[1] / len * w, t[0] / len * w];
+            left.push(p.add_vec(d));
+            right.push(p.sub_vec(d));
+        }
+        left.extend(right.into_iter().rev());
+        left
+    }
+}
+
+/// Joins homotopies end to end, each evaluated over its own sub-interval of `[0, 1]`.
+///
+/// The breaks partition `[0, 1]`, such that piece `i` covers `[breaks[i], breaks[i + 1]]`
+/// and is evaluated with the scalar renormalized to `[0, 1]` over that interval.
+/// For a continuous curve, each piece should end where the next one starts.
+#[derive(Clone)]
+pub struct Piecewise<T> {
+    /// The scalar values where one piece ends and the next one starts.
+    pub breaks: Vec<f64>,
+    /// The pieces.
+    pub pieces: Vec<T>,
+}
+
+impl<T> Piecewise<T> {
+    /// Creates a new piecewise homotopy.
+    ///
+    /// Panics if there are no pieces, if there is not one more break than pieces,
+    /// or if the breaks are not increasing from `0.0` to `1.0`.
+    pub fn new(breaks: Vec<f64>, pieces: Vec<T>) -> Piecewise<T> {
+        assert!(!pieces.is_empty(), "Expected at least one piece");
+        assert_eq!(breaks.len(), pieces.len() + 1, "Expected one more break than pieces");
+        assert!(breaks[0] == 0.0 && breaks[breaks.len() - 1] == 1.0,
+                "Expected breaks to span from `0.0` to `1.0`");
+        assert!(breaks.windows(2).all(|w| w[0] < w[1]), "Expected increasing breaks");
+        Piecewise {breaks, pieces}
+    }
+
+    /// Returns the arc length of each piece of a 2D curve,
+    /// approximated by a polyline with `n_per_segment` segments per piece.
+    ///
+    /// This can be used to place dashes or labels proportionally along the curve.
+    pub fn segment_lengths(&self, n_per_segment: u32) -> Vec<f64>
+        where T: Homotopy<(), Y = [f64; 2]>
+    {
+        self.pieces.iter().map(|piece| {
+            let mut prev = piece.f(());
+            let mut len = 0.0;
+            for i in 1..n_per_segment + 1 {
+                let p = piece.hu(i as f64 / n_per_segment as f64);
+                let d = p.sub_vec(prev);
+                len += (d[0] * d[0] + d[1] * d[1]).sqrt();
+                prev = p;
+            }
+            len
+        }).collect()
+    }
+
+    /// Returns `true` if the tangents match at every joint between two pieces,
+    /// i.e. the curve is tangent-continuous (C1) and not only continuous (C0).
+    ///
+    /// The tangents left and right of each joint are one-sided differences
+    /// with step `1e-6` in the scalar of each piece, scaled by the width of its interval.
+    /// They match when every component differs by at most `eps`, in units of output per scalar,
+    /// so `eps` should be well above the rounding and truncation error of the differences.
+    /// Positional continuity at the joints is not checked.
+    pub fn check_c1<X>(&self, x: X, eps: f64) -> bool
+        where T: Homotopy<X>, T::Y: Vector + ApproxEq, X: Clone
+    {
+        (1..self.pieces.len()).all(|i| {
+            let left = tangent(&self.pieces[i - 1], x.clone(), 1.0, 1e-6)
+                .scale(1.0 / (self.breaks[i] - self.breaks[i - 1]));
+            let right = tangent(&self.pieces[i], x.clone(), 0.0, 1e-6)
+                .scale(1.0 / (self.breaks[i + 1] - self.breaks[i]));
+            left.approx_eq(&right, eps)
+        })
+    }
+}
+
+impl<X, T> Homotopy<X> for Piecewise<T>
+    where T: Homotopy<X>
+{
+    type Y = T::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.pieces[0].f(x)}
+    fn g(&self, x: X) -> Self::Y {self.pieces[self.pieces.len() - 1].g(x)}
+    fn h(&self, x: X, s: f64) -> Self::Y {
+        let last = self.pieces.len() - 1;
+        let i = self.breaks[1..last + 1].iter().position(|&b| s < b).unwrap_or(last);
+        let (a, b) = (self.breaks[i], self.breaks[i + 1]);
+        self.pieces[i].h(x, (s - a) / (b - a))
+    }
+}
+
+/// Bezier homotopy of arbitrary degree.
+///
+/// Maps from the first to the last control point, using de Casteljau's algorithm.
+/// A single control point gives a constant curve.
+/// Panics when evaluated without control points.
+#[derive(Clone)]
+pub struct Bezier<X>(pub Vec<X>);
+
+impl<X> From<QuadraticBezier<X>> for Bezier<X> {
+    fn from(QuadraticBezier(a, b, c): QuadraticBezier<X>) -> Bezier<X> {Bezier(vec![a, b, c])}
+}
+
+impl<X> From<CubicBezier<X>> for Bezier<X> {
+    fn from(CubicBezier(a, b, c, d): CubicBezier<X>) -> Bezier<X> {Bezier(vec![a, b, c, d])}
+}
+
+impl<X> Bezier<X>
+    where X: Vector + Clone
+{
+    /// Returns the centroid of the control points.
+    ///
+    /// Panics if there are no control points.
+    pub fn control_centroid(&self) -> X {
+        let mut points = self.0.iter().cloned();
+        let first = points.next().expect("Expected control points");
+        points.fold(first, |sum, p| sum.add_vec(p)).scale(1.0 / self.0.len() as f64)
+    }
+
+    /// Returns the centroid of the intermediate de Casteljau points at `s`.
+    ///
+    /// These are the points of every level of de Casteljau's algorithm after the control points,
+    /// ending with the point on the curve.
+    /// A single control point is its own centroid.
+    /// Panics if there are no control points.
+    pub fn centroid_at(&self, s: f64) -> X {
+        let mut points = self.0.clone();
+        assert!(!points.is_empty(), "Expected control points");
+        if points.len() == 1 {return points.pop().unwrap()};
+        let mut sum: Option<X> = None;
+        let mut count = 0;
+        while points.len() > 1 {
+            points = points.windows(2)
+                .map(|w| w[0].clone().scale(1.0 - s).add_vec(w[1].clone().scale(s))).collect();
+            for p in &points {
+                sum = Some(match sum {
+                    Some(sum) => sum.add_vec(p.clone()),
+                    None => p.clone(),
+                });
+            }
+            count += points.len();
+        }
+        sum.unwrap().scale(1.0 / count as f64)
+    }
+}
+
+impl<Y> Homotopy<()> for Bezier<Y>
+    where Y: Vector + Clone
+{
+    type Y = Y;
+
+    fn f(&self, _: ()) -> Y {self.0.first().expect("Expected control points").clone()}
+    fn g(&self, _: ()) -> Y {self.0.last().expect("Expected control points").clone()}
+    fn h(&self, _: (), s: f64) -> Y {
+        let mut points = self.0.clone();
+        assert!(!points.is_empty(), "Expected control points");
+        while points.len() > 1 {
+            points = points.windows(2)
+                .map(|w| w[0].clone().scale(1.0 - s).add_vec(w[1].clone().scale(s))).collect();
+        }
+        points.pop().unwrap()
+    }
+}
+
+/// Rational Bezier homotopy with a weight per control point.
+///
+/// Unlike polynomial Bezier curves, rational Bezier curves can represent conic sections exactly,
+/// e.g. a quarter circle with weight `√2/2` on the middle control point.
+/// The weighted points are interpolated with de Casteljau's algorithm
+/// and divided by the interpolated weight.
+#[derive(Clone)]
+pub struct RationalBezier<X> {
+    /// The control points.
+    pub points: Vec<X>,
+    /// The weight of each control point.
+    pub weights: Vec<f64>,
+}
+
+impl<X> RationalBezier<X> {
+    /// Creates a new rational Bezier.
+    ///
+    /// Panics if there are no control points, or if there is not one weight per control point.
+    pub fn new(points: Vec<X>, weights: Vec<f64>) -> RationalBezier<X> {
+        assert!(!points.is_empty(), "Expected control points");
+        assert_eq!(points.len(), weights.len(), "Expected one weight per control point");
+        RationalBezier {points, weights}
+    }
+}
+
+impl<Y> Homotopy<()> for RationalBezier<Y>
+    where Y: Vector + Clone
+{
+    type Y = Y;
+
+    fn f(&self, _: ()) -> Y {self.points[0].clone()}
+    fn g(&self, _: ()) -> Y {self.points[self.points.len() - 1].clone()}
+    fn h(&self, _: (), s: f64) -> Y {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.f(())} else if s == 1.0 {return self.g(())};
+        let mut points: Vec<(Y, f64)> = self.points.iter().zip(self.weights.iter())
+            .map(|(p, &w)| (p.clone().scale(w), w)).collect();
+        while points.len() > 1 {
+            points = points.windows(2).map(|pair| {
+                let (ref a, wa) = pair[0];
+                let (ref b, wb) = pair[1];
+                (a.clone().scale(1.0 - s).add_vec(b.clone().scale(s)), wa * (1.0 - s) + wb * s)
+            }).collect();
+        }
+        let (p, w) = points.pop().unwrap();
+        p.scale(1.0 / w)
+    }
+}
+
+/// A uniform Catmull-Rom spline through a list of waypoints.
+///
+/// Unlike a Bezier curve, the spline passes through every waypoint.
+/// The scalar is spread evenly over the segments, such that waypoint `i`
+/// is reached at `i / (n - 1)` for `n` waypoints.
+///
+/// The tangent at each interior waypoint is half the difference of its neighbors.
+/// At the ends, a phantom waypoint is extrapolated by reflecting the neighbor,
+/// which gives one-sided tangents: with two waypoints, the spline is a straight line
+/// traversed at constant speed, like `Lerp`.
+/// A single waypoint gives a constant curve.
+/// Panics when evaluated without waypoints.
+#[derive(Clone)]
+pub struct CatmullRom<X>(pub Vec<X>);
+
+impl<X> CatmullRom<X>
+    where X: Vector + Clone
+{
+    fn waypoint(&self, i: isize) -> X {
+        let n = self.0.len() as isize;
+        if i < 0 {
+            self.0[0].clone().scale(2.0).sub_vec(self.0[1].clone())
+        } else if i >= n {
+            self.0[n as usize - 1].clone().scale(2.0).sub_vec(self.0[n as usize - 2].clone())
+        } else {
+            self.0[i as usize].clone()
+        }
+    }
+}
+
+impl<Y> Homotopy<()> for CatmullRom<Y>
+    where Y: Vector + Clone
+{
+    type Y = Y;
+
+    fn f(&self, _: ()) -> Y {self.0.first().expect("Expected waypoints").clone()}
+    fn g(&self, _: ()) -> Y {self.0.last().expect("Expected waypoints").clone()}
+    fn h(&self, _: (), s: f64) -> Y {
+        let n = self.0.len();
+        assert!(n > 0, "Expected waypoints");
+        // Handle special cases to get exact values.
+        if n == 1 || s == 0.0 {return self.f(())} else if s == 1.0 {return self.g(())};
+        let pos = s * (n - 1) as f64;
+        let i = (pos.floor().max(0.0) as usize).min(n - 2);
+        let t = pos - i as f64;
+        let i = i as isize;
+        let p1 = self.waypoint(i);
+        let p2 = self.waypoint(i + 1);
+        let m1 = p2.clone().sub_vec(self.waypoint(i - 1)).scale(0.5);
+        let m2 = self.waypoint(i + 2).sub_vec(p1.clone()).scale(0.5);
+        let (t2, t3) = (t * t, t * t * t);
+        p1.scale(2.0 * t3 - 3.0 * t2 + 1.0)
+            .add_vec(m1.scale(t3 - 2.0 * t2 + t))
+            .add_vec(p2.scale(-2.0 * t3 + 3.0 * t2))
+            .add_vec(m2.scale(t3 - t2))
+    }
+}
+
+/// A cubic Hermite curve, given by its endpoints and the tangents at the endpoints.
+#[derive(Copy, Clone)]
+pub struct Hermite<X> {
+    /// The start point.
+    pub p0: X,
+    /// The tangent at the start point.
+    pub m0: X,
+    /// The end point.
+    pub p1: X,
+    /// The tangent at the end point.
+    pub m1: X,
+}
+
+impl<X> Hermite<X>
+    where X: Vector + Clone
+{
+    /// Converts a cubic Bezier into the same curve in Hermite form.
+    ///
+    /// The tangents are three times the differences of the outer control points.
+    pub fn from_bezier(CubicBezier(a, b, c, d): CubicBezier<X>) -> Hermite<X> {
+        Hermite {
+            m0: b.sub_vec(a.clone()).scale(3.0),
+            m1: d.clone().sub_vec(c).scale(3.0),
+            p0: a,
+            p1: d,
+        }
+    }
+}
+
+impl<Y> Homotopy<()> for Hermite<Y>
+    where Y: Vector + Clone
+{
+    type Y = Y;
+
+    fn f(&self, _: ()) -> Y {self.p0.clone()}
+    fn g(&self, _: ()) -> Y {self.p1.clone()}
+    fn h(&self, _: (), s: f64) -> Y {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.f(())} else if s == 1.0 {return self.g(())};
+        let (s2, s3) = (s * s, s * s * s);
+        self.p0.clone().scale(2.0 * s3 - 3.0 * s2 + 1.0)
+            .add_vec(self.m0.clone().scale(s3 - 2.0 * s2 + s))
+            .add_vec(self.p1.clone().scale(-2.0 * s3 + 3.0 * s2))
+            .add_vec(self.m1.clone().scale(s3 - s2))
+    }
+}
+
+/// Plays one homotopy and then another, e.g. for keyframe timelines.
+///
+/// The first homotopy is played over `[0, split]` and the second over `[split, 1]`,
+/// each with the scalar rescaled to `[0, 1]`.
+/// For the result to be continuous, the first homotopy must end where the second starts,
+/// i.e. `first.g(x) == second.f(x)`.
+#[derive(Copy, Clone)]
+pub struct Then<H1, H2> {
+    /// The homotopy played first.
+    pub first: H1,
+    /// The homotopy played second.
+    pub second: H2,
+    /// The scalar where the first homotopy ends and the second starts.
+    pub split: f64,
+}
+
+impl<X, H1, H2> Homotopy<X> for Then<H1, H2>
+    where H1: Homotopy<X>, H2: Homotopy<X, Y = H1::Y>
+{
+    type Y = H1::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.first.f(x)}
+    fn g(&self, x: X) -> Self::Y {self.second.g(x)}
+    fn h(&self, x: X, s: f64) -> Self::Y {
+        // Handle special cases to get exact values.
+        if s == 1.0 {return self.g(x)};
+        if s < self.split {
+            self.first.h(x, s / self.split)
+        } else {
+            self.second.h(x, (s - self.split) / (1.0 - self.split))
+        }
+    }
+}
+
+impl<H1, H2> Then<H1, H2> {
+    /// Returns `true` if the tangents match at the split,
+    /// i.e. the result is tangent-continuous (C1) and not only continuous (C0).
+    ///
+    /// The tangents are computed and compared the same way as in `Piecewise::check_c1`.
+    pub fn check_c1<X>(&self, x: X, eps: f64) -> bool
+        where H1: Homotopy<X>, H2: Homotopy<X, Y = H1::Y>, H1::Y: Vector + ApproxEq, X: Clone
+    {
+        let left = tangent(&self.first, x.clone(), 1.0, 1e-6).scale(1.0 / self.split);
+        let right = tangent(&self.second, x, 0.0, 1e-6).scale(1.0 / (1.0 - self.split));
+        left.approx_eq(&right, eps)
+    }
+}
+
+/// Interpolates between keyframes, given as values at sorted times in `[0, 1]`.
+///
+/// Between two keyframes, the values are interpolated linearly,
+/// with the scalar of each segment remapped by that segment's easing.
+#[derive(Clone)]
+pub struct Keyframes<X> {
+    /// The times of the keyframes, sorted from `0.0` to `1.0`.
+    pub times: Vec<f64>,
+    /// The values of the keyframes.
+    pub values: Vec<X>,
+    /// The easing of each segment between two keyframes.
+    pub easings: Vec<Easing>,
+}
+
+impl<X> Keyframes<X> {
+    /// Creates new keyframes with linear interpolation between them.
+    ///
+    /// Panics if there are less than two keyframes, if there is not one time per value,
+    /// or if the times are not sorted from `0.0` to `1.0`.
+    pub fn new(times: Vec<f64>, values: Vec<X>) -> Keyframes<X> {
+        assert!(times.len() >= 2, "Expected at least two keyframes");
+        assert_eq!(times.len(), values.len(), "Expected one time per value");
+        assert!(times[0] == 0.0 && times[times.len() - 1] == 1.0,
+                "Expected times to span from `0.0` to `1.0`");
+        assert!(times.windows(2).all(|w| w[0] < w[1]), "Expected increasing times");
+        let easings = vec![Easing::Linear; times.len() - 1];
+        Keyframes {times, values, easings}
+    }
+
+    /// Sets the easing of each segment between two keyframes.
+    ///
+    /// Panics if there is not one easing per segment.
+    pub fn with_easings(self, easings: Vec<Easing>) -> Keyframes<X> {
+        assert_eq!(easings.len(), self.times.len() - 1, "Expected one easing per segment");
+        Keyframes {easings, ..self}
+    }
+}
+
+impl<Y> Homotopy<()> for Keyframes<Y>
+    where Y: Mul<f64, Output = Y> + Add<Output = Y> + Clone
+{
+    type Y = Y;
+
+    fn f(&self, _: ()) -> Y {self.values[0].clone()}
+    fn g(&self, _: ()) -> Y {self.values[self.values.len() - 1].clone()}
+    fn h(&self, _: (), s: f64) -> Y {
+        let last = self.times.len() - 2;
+        let i = self.times[1..last + 1].iter().position(|&t| s < t).unwrap_or(last);
+        let (a, b) = (self.times[i], self.times[i + 1]);
+        let t = self.easings[i].apply((s - a) / (b - a));
+        Lerp(self.values[i].clone(), self.values[i + 1].clone()).h((), t)
+    }
+}
+
+/// Crossfades between two keyframe timelines sharing the same value type.
+///
+/// The input is the time in `[0, 1]` of both timelines,
+/// and the scalar blends from the value of the first timeline to that of the second.
+/// This is useful for retargeting one whole animation into another.
+#[derive(Clone)]
+pub struct TimelineBlend<Y>(pub Keyframes<Y>, pub Keyframes<Y>);
+
+impl<Y> Homotopy<f64> for TimelineBlend<Y>
+    where Y: Mul<f64, Output = Y> + Add<Output = Y> + Clone
+{
+    type Y = Y;
+
+    fn f(&self, t: f64) -> Y {self.0.hu(t)}
+    fn g(&self, t: f64) -> Y {self.1.hu(t)}
+    fn h(&self, t: f64, s: f64) -> Y {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.f(t)} else if s == 1.0 {return self.g(t)};
+        Lerp(self.f(t), self.g(t)).h((), s)
+    }
+}
@@ -0,0 +1,175 @@
+use super::*;
+
+/// Tiles the output of a 2D homotopy into an `n × n` mirrored grid.
+///
+/// The output of the wrapped homotopy is assumed to lie in the unit square.
+/// As the scalar sweeps, it traces the wrapped homotopy once per cell,
+/// going through the cells row by row, from `[0, 0]` to `[1, 1]`.
+/// Each cell is scaled down to `1/n` of the unit square,
+/// and the cells in odd columns are mirrored in x and cells in odd rows are mirrored in y,
+/// such that neighboring tiles reflect each other like a kaleidoscope.
+///
+/// The output jumps between the end of one cell and the start of the next.
+/// Panics when evaluated if `n` is zero.
+#[derive(Copy, Clone)]
+pub struct RepeatMirror<T>(pub T, pub u32);
+
+impl<X, T> Homotopy<X> for RepeatMirror<T>
+    where T: Homotopy<X, Y = [f64; 2]>
+{
+    type Y = [f64; 2];
+
+    fn f(&self, x: X) -> Self::Y {self.h(x, 0.0)}
+    fn g(&self, x: X) -> Self::Y {self.h(x, 1.0)}
+    fn h(&self, x: X, s: f64) -> Self::Y {
+        let n = self.1;
+        assert!(n >= 1, "Expected at least one cell per row");
+        let cells = n * n;
+        let k = ((s * cells as f64).floor() as u32).min(cells - 1);
+        let t = s * cells as f64 - k as f64;
+        let (i, j) = (k % n, k / n);
+        let p = self.0.h(x, t);
+        let px = if i % 2 == 0 {p[0]} else {1.0 - p[0]};
+        let py = if j % 2 == 0 {p[1]} else {1.0 - p[1]};
+        [(i as f64 + px) / n as f64, (j as f64 + py) / n as f64]
+    }
+}
+
+/// Repeats the output of a 2D homotopy with `n`-fold rotational symmetry around the origin.
+///
+/// The wrapped homotopy is assumed to trace one wedge of angle `2π/n`.
+/// As the scalar sweeps, it traces the wrapped homotopy once per wedge,
+/// each copy rotated by `2π k/n` counter-clockwise for the `k`-th wedge,
+/// such that sampling the whole scalar range gives the full symmetric set of points.
+///
+/// The output jumps between the end of one wedge and the start of the next,
+/// unless the wrapped homotopy ends where the next rotated copy starts.
+/// The number of wedges `n` must be at least 1, otherwise this panics when evaluated.
+#[derive(Copy, Clone)]
+pub struct Symmetrize<T>(pub T, pub u32);
+
+impl<X, T> Homotopy<X> for Symmetrize<T>
+    where T: Homotopy<X, Y = [f64; 2]>
+{
+    type Y = [f64; 2];
+
+    fn f(&self, x: X) -> Self::Y {self.h(x, 0.0)}
+    fn g(&self, x: X) -> Self::Y {self.h(x, 1.0)}
+    fn h(&self, x: X, s: f64) -> Self::Y {
+        let n = self.1;
+        assert!(n >= 1, "Expected at least one wedge");
+        let k = ((s * n as f64).floor() as u32).min(n - 1);
+        let t = s * n as f64 - k as f64;
+        let p = self.0.h(x, t);
+        let angle = 2.0 * ::std::f64::consts::PI * k as f64 / n as f64;
+        let (sin, cos) = angle.sin_cos();
+        [cos * p[0] - sin * p[1], sin * p[0] + cos * p[1]]
+    }
+}
+
+/// Mixes the bits of a 64 bit integer, using the SplitMix64 finalizer.
+fn mix64(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Displaces the output of a 2D homotopy by a deterministic pseudo-random offset.
+///
+/// The fields are the wrapped homotopy, the seed and the amplitude.
+/// The offset is computed by hashing the seed together with the scalar,
+/// so the same seed reproduces the same output across runs.
+/// Each offset component lies within `[-amplitude, amplitude]`.
+///
+/// The output is not continuous in the scalar, since even neighboring scalars
+/// get unrelated offsets. Only `0.0` and `-0.0` are treated as the same scalar.
+#[derive(Copy, Clone)]
+pub struct Jitter<T>(pub T, pub u64, pub f64);
+
+impl<T> Jitter<T> {
+    fn offset(&self, s: f64) -> [f64; 2] {
+        // Adding zero turns `-0.0` into `0.0`, so both give the same offset.
+        let a = mix64(self.1 ^ (s + 0.0).to_bits());
+        let b = mix64(a);
+        let unit = |v: u64| (v >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0;
+        [unit(a) * self.2, unit(b) * self.2]
+    }
+}
+
+impl<X, T> Homotopy<X> for Jitter<T>
+    where T: Homotopy<X, Y = [f64; 2]>
+{
+    type Y = [f64; 2];
+
+    fn f(&self, x: X) -> Self::Y {self.0.f(x).add_vec(self.offset(0.0))}
+    fn g(&self, x: X) -> Self::Y {self.0.g(x).add_vec(self.offset(1.0))}
+    fn h(&self, x: X, s: f64) -> Self::Y {self.0.h(x, s).add_vec(self.offset(s))}
+}
+
+/// Selects between two 2D homotopies in a checkerboard pattern.
+///
+/// The scalar square is divided into an `n × n` grid of cells.
+/// Cells where the sum of the column and row is even use the first homotopy,
+/// and the other cells use the second.
+/// The corner cells at `[0, 0]` and `[1, 1]` both use the first homotopy,
+/// so `f` and `g` are taken from it.
+///
+/// The output is discontinuous at the cell boundaries, unless both homotopies agree there.
+/// Panics when evaluated if `n` is zero.
+#[derive(Copy, Clone)]
+pub struct Checker<A, B>(pub A, pub B, pub u32);
+
+impl<X, A, B> Homotopy<X, [f64; 2]> for Checker<A, B>
+    where A: Homotopy<X, [f64; 2]>, B: Homotopy<X, [f64; 2], Y = A::Y>
+{
+    type Y = A::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.0.f(x)}
+    fn g(&self, x: X) -> Self::Y {self.0.g(x)}
+    fn h(&self, x: X, s: [f64; 2]) -> Self::Y {
+        let n = self.2;
+        assert!(n >= 1, "Expected at least one cell per row");
+        let cell = |t: f64| ((t * n as f64).floor().max(0.0) as u32).min(n - 1);
+        if (cell(s[0]) + cell(s[1])) % 2 == 0 {self.0.h(x, s)} else {self.1.h(x, s)}
+    }
+}
+
+/// Shifts the phase of a closed-loop homotopy by an offset, e.g. to stagger looping animations.
+///
+/// The scalar is shifted by the offset and wrapped around into `[0, 1)`.
+/// This requires the wrapped homotopy to be closed, such as `Circle`, where `f` equals `g`,
+/// otherwise the output jumps where the scalar wraps around.
+#[derive(Copy, Clone)]
+pub struct PhaseShift<T>(pub T, pub f64);
+
+impl<X, T> Homotopy<X> for PhaseShift<T>
+    where T: Homotopy<X>
+{
+    type Y = T::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.h(x, 0.0)}
+    fn g(&self, x: X) -> Self::Y {self.h(x, 1.0)}
+    fn h(&self, x: X, s: f64) -> Self::Y {self.0.h(x, (s + self.1).rem_euclid(1.0))}
+}
+
+/// Wraps any scalar into `[0, 1)` before passing it to a closed-loop homotopy.
+///
+/// This allows passing unbounded, accumulating time to looping animations.
+/// Unlike clamping, which saturates at the ends, the scalar wraps around,
+/// so `1.25` maps to `0.25`.
+/// This is `PhaseShift` with a zero offset, and delegates to it.
+/// Like `PhaseShift`, this requires the wrapped homotopy to be closed, such as `Circle`,
+/// where `f` equals `g`, since `1.0` wraps around to `0.0`.
+#[derive(Copy, Clone)]
+pub struct WrapScalar<T>(pub T);
+
+impl<X, T> Homotopy<X> for WrapScalar<T>
+    where T: Homotopy<X>
+{
+    type Y = T::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.h(x, 0.0)}
+    fn g(&self, x: X) -> Self::Y {self.h(x, 1.0)}
+    fn h(&self, x: X, s: f64) -> Self::Y {PhaseShift(&self.0, 0.0).h(x, s)}
+}
@@ -0,0 +1,30 @@
+use super::*;
+
+/// Plays back a homotopy by stepping its scalar over time.
+///
+/// This is a stateful layer on top of a homotopy, e.g. for scrubbable timelines.
+/// The position is kept within `[0, 1]`, and a negative speed plays the homotopy in reverse.
+#[derive(Copy, Clone)]
+pub struct Player<T> {
+    /// The homotopy being played.
+    pub homotopy: T,
+    /// The current scalar.
+    pub position: f64,
+    /// The change in scalar per unit of time.
+    pub speed: f64,
+}
+
+impl<T> Player<T> {
+    /// Creates a new player at the start, playing forward at unit speed.
+    pub fn new(homotopy: T) -> Player<T> {
+        Player {homotopy, position: 0.0, speed: 1.0}
+    }
+
+    /// Steps the position by `speed * dt` and evaluates the homotopy at the new position.
+    pub fn advance<X>(&mut self, dt: f64) -> T::Y
+        where T: Homotopy<X>, X: Default
+    {
+        self.position = (self.position + self.speed * dt).clamp(0.0, 1.0);
+        self.homotopy.hu(self.position)
+    }
+}
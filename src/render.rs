@@ -0,0 +1,40 @@
+use super::*;
+
+use std::io;
+
+/// Renders an animation of a 2D homotopy as a sequence of PNG frames.
+///
+/// Frame `i` fixes the first scalar component to `i / (frames - 1)`, like `left_right`,
+/// and plots the curve traced by the second component with `resolution` samples.
+/// Points outside the axis-aligned bounding box `aabb` (minimum and maximum corner) are skipped.
+/// The frames are written to `{file_prefix}-{i:04}.png` as `size × size` images.
+pub fn render_frames<H, X>(
+    h: &H,
+    frames: u32,
+    size: u32,
+    aabb: ([f64; 2], [f64; 2]),
+    resolution: u32,
+    file_prefix: &str,
+) -> io::Result<()>
+    where H: Homotopy<X, [f64; 2], Y = [f64; 2]>, X: Default
+{
+    use image::{Rgba, RgbaImage};
+
+    for i in 0..frames {
+        let t = if frames > 1 {i as f64 / (frames - 1) as f64} else {0.0};
+        let curve = h.left_right(t);
+        let mut image = RgbaImage::from_pixel(size, size, Rgba {data: [255; 4]});
+        for j in 0..resolution + 1 {
+            let pos = curve.hu(j as f64 / resolution as f64);
+            if pos[0] < aabb.0[0] ||
+               pos[1] < aabb.0[1] ||
+               pos[0] >= aabb.1[0] ||
+               pos[1] >= aabb.1[1] {continue};
+            let x = (pos[0] - aabb.0[0]) / (aabb.1[0] - aabb.0[0]) * size as f64;
+            let y = (pos[1] - aabb.0[1]) / (aabb.1[1] - aabb.0[1]) * size as f64;
+            image.put_pixel(x as u32, y as u32, Rgba {data: [0, 0, 0, 255]});
+        }
+        image.save(format!("{}-{:04}.png", file_prefix, i))?;
+    }
+    Ok(())
+}
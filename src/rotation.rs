@@ -0,0 +1,418 @@
+use super::*;
+
+fn dot4(a: [f64; 4], b: [f64; 4]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2] + a[3] * b[3]
+}
+
+fn normalize4(a: [f64; 4]) -> [f64; 4] {
+    a.scale(1.0 / dot4(a, a).sqrt())
+}
+
+/// Spherical linear interpolation between two unit quaternions, without flipping signs.
+///
+/// Falls back to normalized linear interpolation when the quaternions are nearly identical.
+fn slerp4(a: [f64; 4], b: [f64; 4], s: f64) -> [f64; 4] {
+    // Handle special cases to get exact values.
+    if s == 0.0 {return a} else if s == 1.0 {return b};
+    let d = dot4(a, b).clamp(-1.0, 1.0);
+    if d > 0.9995 {
+        return normalize4(a.scale(1.0 - s).add_vec(b.scale(s)));
+    }
+    let theta = d.acos();
+    let sin = theta.sin();
+    a.scale(((1.0 - s) * theta).sin() / sin).add_vec(b.scale((s * theta).sin() / sin))
+}
+
+/// Spherical cubic interpolation (SQUAD) of unit quaternions.
+///
+/// Quaternions are stored as `[x, y, z, w]`.
+/// Maps from the first quaternion to the last, using the two middle quaternions
+/// as inner control points, similar to a cubic Bezier.
+/// The result stays on the unit sphere and gives smooth orientation splines.
+#[derive(Copy, Clone)]
+pub struct Squad(pub [f64; 4], pub [f64; 4], pub [f64; 4], pub [f64; 4]);
+
+impl Homotopy<()> for Squad {
+    type Y = [f64; 4];
+
+    fn f(&self, _: ()) -> Self::Y {self.0}
+    fn g(&self, _: ()) -> Self::Y {self.3}
+    fn h(&self, _: (), s: f64) -> Self::Y {
+        let a = slerp4(self.0, self.3, s);
+        let b = slerp4(self.1, self.2, s);
+        slerp4(a, b, 2.0 * s * (1.0 - s))
+    }
+}
+
+/// Walks the great circle between two geographic points on the unit sphere.
+///
+/// Points are given as `[latitude, longitude]` in radians.
+/// When the points are antipodal, the great circle is not unique,
+/// so the path passes through the poles (or through longitude zero when starting at a pole).
+#[derive(Copy, Clone)]
+pub struct GeoSlerp {
+    /// The start point.
+    pub from: [f64; 2],
+    /// The end point.
+    pub to: [f64; 2],
+}
+
+impl Homotopy<()> for GeoSlerp {
+    type Y = [f64; 2];
+
+    fn f(&self, _: ()) -> Self::Y {self.from}
+    fn g(&self, _: ()) -> Self::Y {self.to}
+    fn h(&self, _: (), s: f64) -> Self::Y {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.from} else if s == 1.0 {return self.to};
+        let to_xyz = |p: [f64; 2]| [p[0].cos() * p[1].cos(), p[0].cos() * p[1].sin(), p[0].sin()];
+        let a = to_xyz(self.from);
+        let b = to_xyz(self.to);
+        let d = dot3(a, b).clamp(-1.0, 1.0);
+        let theta = d.acos();
+        let p = if d < -0.9999999 {
+            let mut axis = cross3(a, [0.0, 0.0, 1.0]);
+            if dot3(axis, axis) < 1e-12 {axis = cross3(a, [1.0, 0.0, 0.0])};
+            let perp = cross3(normalize3(axis), a);
+            a.scale((s * theta).cos()).add_vec(perp.scale((s * theta).sin()))
+        } else if d > 0.9999999 {
+            normalize3(a.scale(1.0 - s).add_vec(b.scale(s)))
+        } else {
+            let sin = theta.sin();
+            a.scale(((1.0 - s) * theta).sin() / sin).add_vec(b.scale((s * theta).sin() / sin))
+        };
+        [p[2].clamp(-1.0, 1.0).asin(), p[1].atan2(p[0])]
+    }
+}
+
+fn mat3_mul(a: [[f64; 3]; 3], b: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let mut res = [[0.0; 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            res[i][j] = a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j];
+        }
+    }
+    res
+}
+
+fn mat3_transpose(a: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    [
+        [a[0][0], a[1][0], a[2][0]],
+        [a[0][1], a[1][1], a[2][1]],
+        [a[0][2], a[1][2], a[2][2]],
+    ]
+}
+
+fn mat3_transform(a: [[f64; 3]; 3], p: [f64; 3]) -> [f64; 3] {
+    [dot3(a[0], p), dot3(a[1], p), dot3(a[2], p)]
+}
+
+/// Returns the rotation vector (axis times angle) of a rotation matrix.
+fn rotation_log(r: [[f64; 3]; 3]) -> [f64; 3] {
+    let cos = ((r[0][0] + r[1][1] + r[2][2] - 1.0) * 0.5).clamp(-1.0, 1.0);
+    let angle = cos.acos();
+    let skew = [r[2][1] - r[1][2], r[0][2] - r[2][0], r[1][0] - r[0][1]];
+    if angle < 1e-9 {
+        [0.0; 3]
+    } else if angle > ::std::f64::consts::PI - 1e-6 {
+        // Near a half turn, the skew part vanishes, so read the axis from the diagonal.
+        let mut axis = [
+            ((r[0][0] + 1.0) * 0.5).max(0.0).sqrt(),
+            ((r[1][1] + 1.0) * 0.5).max(0.0).sqrt(),
+            ((r[2][2] + 1.0) * 0.5).max(0.0).sqrt(),
+        ];
+        if axis[0] >= axis[1] && axis[0] >= axis[2] {
+            if r[0][1] + r[1][0] < 0.0 {axis[1] = -axis[1]};
+            if r[0][2] + r[2][0] < 0.0 {axis[2] = -axis[2]};
+        } else if axis[1] >= axis[2] {
+            if r[0][1] + r[1][0] < 0.0 {axis[0] = -axis[0]};
+            if r[1][2] + r[2][1] < 0.0 {axis[2] = -axis[2]};
+        } else {
+            if r[0][2] + r[2][0] < 0.0 {axis[0] = -axis[0]};
+            if r[1][2] + r[2][1] < 0.0 {axis[1] = -axis[1]};
+        }
+        normalize3(axis).scale(angle)
+    } else {
+        skew.scale(angle / (2.0 * angle.sin()))
+    }
+}
+
+/// Returns the rotation matrix of a rotation vector (axis times angle).
+fn rotation_exp(v: [f64; 3]) -> [[f64; 3]; 3] {
+    let angle = dot3(v, v).sqrt();
+    if angle < 1e-12 {return [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]};
+    let [x, y, z] = v.scale(1.0 / angle);
+    let (sin, cos) = angle.sin_cos();
+    let c = 1.0 - cos;
+    [
+        [cos + x * x * c, x * y * c - z * sin, x * z * c + y * sin],
+        [y * x * c + z * sin, cos + y * y * c, y * z * c - x * sin],
+        [z * x * c - y * sin, z * y * c + x * sin, cos + z * z * c],
+    ]
+}
+
+/// Interpolates between two rotation matrices along the geodesic rotation path.
+///
+/// This is the matrix analog of quaternion slerp, computed as `R1 exp(s log(R1ᵀ R2))`,
+/// which rotates with constant angular velocity about a fixed axis.
+/// The input is a point that gets transformed by the interpolated rotation.
+#[derive(Copy, Clone)]
+pub struct MinTorqueLerp(pub [[f64; 3]; 3], pub [[f64; 3]; 3]);
+
+impl MinTorqueLerp {
+    /// Returns the interpolated rotation matrix at `s`.
+    pub fn matrix_at(&self, s: f64) -> [[f64; 3]; 3] {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.0} else if s == 1.0 {return self.1};
+        let rel = rotation_log(mat3_mul(mat3_transpose(self.0), self.1));
+        mat3_mul(self.0, rotation_exp(rel.scale(s)))
+    }
+}
+
+impl Homotopy<[f64; 3]> for MinTorqueLerp {
+    type Y = [f64; 3];
+
+    fn f(&self, p: [f64; 3]) -> Self::Y {mat3_transform(self.0, p)}
+    fn g(&self, p: [f64; 3]) -> Self::Y {mat3_transform(self.1, p)}
+    fn h(&self, p: [f64; 3], s: f64) -> Self::Y {mat3_transform(self.matrix_at(s), p)}
+}
+
+/// Interpolates between two 3×3 matrices element by element, transforming a point.
+///
+/// When `orthonormalize` is `true`, the blended matrix is re-orthonormalized
+/// with Gram-Schmidt on its columns, which keeps blends of rotations rigid.
+/// When `false`, the raw element-wise blend is used, which suits general linear maps.
+#[derive(Copy, Clone)]
+pub struct MatrixLerp {
+    /// The matrix being mapped from.
+    pub a: [[f64; 3]; 3],
+    /// The matrix being mapped to.
+    pub b: [[f64; 3]; 3],
+    /// Whether to re-orthonormalize the blended matrix.
+    pub orthonormalize: bool,
+}
+
+impl MatrixLerp {
+    /// Returns the blended matrix at `s`.
+    pub fn matrix_at(&self, s: f64) -> [[f64; 3]; 3] {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.a} else if s == 1.0 {return self.b};
+        let mut m = [[0.0; 3]; 3];
+        for (i, row) in m.iter_mut().enumerate() {
+            *row = self.a[i].scale(1.0 - s).add_vec(self.b[i].scale(s));
+        }
+        if !self.orthonormalize {return m};
+        let t = mat3_transpose(m);
+        let c0 = normalize3(t[0]);
+        let c1 = normalize3(t[1].sub_vec(c0.scale(dot3(c0, t[1]))));
+        let c2 = cross3(c0, c1);
+        mat3_transpose([c0, c1, c2])
+    }
+}
+
+impl Homotopy<[f64; 3]> for MatrixLerp {
+    type Y = [f64; 3];
+
+    fn f(&self, p: [f64; 3]) -> Self::Y {mat3_transform(self.a, p)}
+    fn g(&self, p: [f64; 3]) -> Self::Y {mat3_transform(self.b, p)}
+    fn h(&self, p: [f64; 3], s: f64) -> Self::Y {mat3_transform(self.matrix_at(s), p)}
+}
+
+/// A camera pose given by an eye position, a target to look at and an up direction.
+#[derive(Copy, Clone)]
+pub struct Camera {
+    /// The position of the camera.
+    pub eye: [f64; 3],
+    /// The point the camera looks at.
+    pub target: [f64; 3],
+    /// The up direction of the camera.
+    pub up: [f64; 3],
+}
+
+impl Camera {
+    /// Returns the rotation part of the view matrix, with rows right, up and backward.
+    fn rotation(&self) -> [[f64; 3]; 3] {
+        let forward = normalize3(self.target.sub_vec(self.eye));
+        let right = normalize3(cross3(forward, self.up));
+        let up = cross3(right, forward);
+        [right, up, forward.scale(-1.0)]
+    }
+
+    /// Returns the right-handed look-at view matrix, in row-major order.
+    pub fn view_matrix(&self) -> [[f64; 4]; 4] {
+        view_matrix(self.rotation(), self.eye)
+    }
+}
+
+/// Builds a view matrix from a rotation and an eye position.
+fn view_matrix(r: [[f64; 3]; 3], eye: [f64; 3]) -> [[f64; 4]; 4] {
+    let t = mat3_transform(r, eye);
+    [
+        [r[0][0], r[0][1], r[0][2], -t[0]],
+        [r[1][0], r[1][1], r[1][2], -t[1]],
+        [r[2][0], r[2][1], r[2][2], -t[2]],
+        [0.0, 0.0, 0.0, 1.0],
+    ]
+}
+
+/// Morphs between two camera poses.
+///
+/// The orientation is interpolated along the shortest rotation, like a slerp,
+/// while the eye position is interpolated linearly.
+/// This avoids the wobble of interpolating Euler angles or look-at targets.
+#[derive(Copy, Clone)]
+pub struct CameraMorph {
+    /// The camera pose being mapped from.
+    pub from: Camera,
+    /// The camera pose being mapped to.
+    pub to: Camera,
+}
+
+impl CameraMorph {
+    /// Returns the view matrix at `s`.
+    pub fn view_matrix_at(&self, s: f64) -> [[f64; 4]; 4] {
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.f(())} else if s == 1.0 {return self.g(())};
+        let r = MinTorqueLerp(self.from.rotation(), self.to.rotation()).matrix_at(s);
+        let eye = self.from.eye.scale(1.0 - s).add_vec(self.to.eye.scale(s));
+        view_matrix(r, eye)
+    }
+}
+
+impl Homotopy<()> for CameraMorph {
+    type Y = [[f64; 4]; 4];
+
+    fn f(&self, _: ()) -> Self::Y {self.from.view_matrix()}
+    fn g(&self, _: ()) -> Self::Y {self.to.view_matrix()}
+    fn h(&self, _: (), s: f64) -> Self::Y {self.view_matrix_at(s)}
+}
+
+/// Blends two surface normals along the great circle between them.
+///
+/// Unlike a linear blend, the result stays unit length.
+/// The normals do not need to be normalized.
+/// For opposite normals, the blend rotates through an arbitrary perpendicular direction.
+pub fn blend_normal(n0: [f64; 3], n1: [f64; 3], s: f64) -> [f64; 3] {
+    let a = normalize3(n0);
+    let b = normalize3(n1);
+    // Handle special cases to get exact values.
+    if s == 0.0 {return a} else if s == 1.0 {return b};
+    let d = dot3(a, b).clamp(-1.0, 1.0);
+    let perp = b.sub_vec(a.scale(d));
+    if dot3(perp, perp) < 1e-24 {
+        if d > 0.0 {return a};
+        let axis = if a[0].abs() <= a[1].abs() && a[0].abs() <= a[2].abs() {[1.0, 0.0, 0.0]}
+            else if a[1].abs() <= a[2].abs() {[0.0, 1.0, 0.0]}
+            else {[0.0, 0.0, 1.0]};
+        let p = normalize3(axis.sub_vec(a.scale(dot3(axis, a))));
+        let angle = s * std::f64::consts::PI;
+        return a.scale(angle.cos()).add_vec(p.scale(angle.sin()));
+    }
+    let angle = s * d.acos();
+    a.scale(angle.cos()).add_vec(normalize3(perp).scale(angle.sin()))
+}
+
+/// Spherical linear interpolation between two unit vectors, along the great circle.
+///
+/// The inputs are normalized, so `f` and `g` return unit vectors.
+/// When the vectors are nearly parallel, this falls back to normalized linear interpolation
+/// to avoid dividing by a vanishing `sin θ`.
+/// Opposite vectors have no unique great circle, so an arbitrary one is picked,
+/// like in `blend_normal`.
+#[derive(Copy, Clone)]
+pub struct Slerp(pub [f64; 3], pub [f64; 3]);
+
+impl Homotopy<()> for Slerp {
+    type Y = [f64; 3];
+
+    fn f(&self, _: ()) -> [f64; 3] {normalize3(self.0)}
+    fn g(&self, _: ()) -> [f64; 3] {normalize3(self.1)}
+    fn h(&self, _: (), s: f64) -> [f64; 3] {
+        let a = self.f(());
+        let b = self.g(());
+        // Handle special cases to get exact values.
+        if s == 0.0 {return a} else if s == 1.0 {return b};
+        let d = dot3(a, b).clamp(-1.0, 1.0);
+        let theta = d.acos();
+        let sin_theta = theta.sin();
+        if sin_theta < 1e-6 {
+            if d < 0.0 {return blend_normal(a, b, s)};
+            return normalize3(a.scale(1.0 - s).add_vec(b.scale(s)));
+        }
+        let wa = ((1.0 - s) * theta).sin() / sin_theta;
+        let wb = (s * theta).sin() / sin_theta;
+        a.scale(wa).add_vec(b.scale(wb))
+    }
+}
+
+/// Spherical linear interpolation between two rotations given as quaternions.
+///
+/// Quaternions are stored as `[x, y, z, w]` and are normalized, so `f` and `g` return
+/// unit quaternions.
+/// Since `q` and `-q` represent the same rotation, `g` flips the sign of the second quaternion
+/// when the dot product is negative, such that the rotation takes the shortest path
+/// and `h` ends continuously at `g`.
+/// Falls back to normalized linear interpolation when the quaternions are nearly identical.
+#[derive(Copy, Clone)]
+pub struct QuatSlerp(pub [f64; 4], pub [f64; 4]);
+
+impl Homotopy<()> for QuatSlerp {
+    type Y = [f64; 4];
+
+    fn f(&self, _: ()) -> Self::Y {normalize4(self.0)}
+    fn g(&self, _: ()) -> Self::Y {
+        let b = normalize4(self.1);
+        if dot4(self.f(()), b) < 0.0 {b.scale(-1.0)} else {b}
+    }
+    fn h(&self, _: (), s: f64) -> Self::Y {slerp4(self.f(()), self.g(()), s)}
+}
+
+/// Keyframed orientations, interpolated by spherical linear interpolation.
+///
+/// Each keyframe is a `(time, quaternion)` pair, where quaternions are stored as `[x, y, z, w]`.
+/// The times are sorted and span from `0.0` to `1.0`, which is checked by `new`.
+/// Since `q` and `-q` represent the same rotation, `new` also normalizes the quaternions
+/// and flips the sign of each one that points away from the previous one,
+/// such that every segment takes the shortest path and the output is continuous across keys.
+/// At each key time, the output is exactly the normalized keyed orientation,
+/// or its negation when the sign was flipped.
+#[derive(Clone)]
+pub struct QuatKeyframes(pub Vec<(f64, [f64; 4])>);
+
+impl QuatKeyframes {
+    /// Creates new keyframed orientations.
+    ///
+    /// Panics if there are less than two keyframes,
+    /// or if the times are not sorted from `0.0` to `1.0`.
+    pub fn new(keys: Vec<(f64, [f64; 4])>) -> QuatKeyframes {
+        assert!(keys.len() >= 2, "Expected at least two keyframes");
+        assert!(keys[0].0 == 0.0 && keys[keys.len() - 1].0 == 1.0,
+                "Expected times to span from `0.0` to `1.0`");
+        assert!(keys.windows(2).all(|w| w[0].0 < w[1].0), "Expected increasing times");
+        let mut aligned: Vec<(f64, [f64; 4])> = Vec::with_capacity(keys.len());
+        for (t, q) in keys {
+            let q = normalize4(q);
+            let q = match aligned.last() {
+                Some(&(_, prev)) if dot4(prev, q) < 0.0 => q.scale(-1.0),
+                _ => q,
+            };
+            aligned.push((t, q));
+        }
+        QuatKeyframes(aligned)
+    }
+}
+
+impl Homotopy<()> for QuatKeyframes {
+    type Y = [f64; 4];
+
+    fn f(&self, _: ()) -> Self::Y {self.0[0].1}
+    fn g(&self, _: ()) -> Self::Y {self.0[self.0.len() - 1].1}
+    fn h(&self, _: (), s: f64) -> Self::Y {
+        let keys = &self.0;
+        // Handle special cases to get exact values.
+        if s == 0.0 {return self.f(())} else if s == 1.0 {return self.g(())};
+        let i = keys[1..].iter().position(|key| key.0 > s).unwrap_or(keys.len() - 2);
+        let ((t0, a), (t1, b)) = (keys[i], keys[i + 1]);
+        slerp4(a, b, ((s - t0) / (t1 - t0)).clamp(0.0, 1.0))
+    }
+}
@@ -363,3 +363,46 @@ impl<X, T> Homotopy<X, [f64; 3]> for PastFuture<T>
     fn g(&self, x: X) -> Self::Y {self.0.h(x, [1.0, 1.0, 1.0, self.1])}
     fn h(&self, x: X, s: [f64; 3]) -> Self::Y {self.0.h(x, [s[0], s[1], s[2], self.1])}
 }
+
+/// Fixes an arbitrary set of axes of a 3D homotopy, given as `(axis, value)` pairs.
+///
+/// The remaining axes are controlled by the scalar, in order.
+/// Fixing two axes gives a 1D homotopy and fixing one axis gives a 2D homotopy.
+/// Panics when evaluated if the number of fixed axes does not match the scalar.
+#[derive(Clone)]
+pub struct FixedAxes<T>(pub T, pub Vec<(usize, f64)>);
+
+impl<T> FixedAxes<T> {
+    fn fill(&self, free: &[f64]) -> [f64; 3] {
+        assert_eq!(self.1.len() + free.len(), 3, "Expected fixed and free axes to add up to 3");
+        let mut free = free.iter();
+        let mut s = [0.0; 3];
+        for (i, v) in s.iter_mut().enumerate() {
+            *v = match self.1.iter().find(|&&(axis, _)| axis == i) {
+                Some(&(_, value)) => value,
+                None => *free.next().expect("Expected distinct axes in the range 0..3"),
+            };
+        }
+        s
+    }
+}
+
+impl<X, T> Homotopy<X> for FixedAxes<T>
+    where T: Homotopy<X, [f64; 3]>
+{
+    type Y = T::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.0.h(x, self.fill(&[0.0]))}
+    fn g(&self, x: X) -> Self::Y {self.0.h(x, self.fill(&[1.0]))}
+    fn h(&self, x: X, s: f64) -> Self::Y {self.0.h(x, self.fill(&[s]))}
+}
+
+impl<X, T> Homotopy<X, [f64; 2]> for FixedAxes<T>
+    where T: Homotopy<X, [f64; 3]>
+{
+    type Y = T::Y;
+
+    fn f(&self, x: X) -> Self::Y {self.0.h(x, self.fill(&[0.0, 0.0]))}
+    fn g(&self, x: X) -> Self::Y {self.0.h(x, self.fill(&[1.0, 1.0]))}
+    fn h(&self, x: X, s: [f64; 2]) -> Self::Y {self.0.h(x, self.fill(&s))}
+}
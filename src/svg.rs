@@ -0,0 +1,77 @@
+/// An error from parsing an SVG path.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SvgParseError {
+    /// A number could not be parsed.
+    InvalidNumber(String),
+    /// A character that is not part of the path syntax.
+    UnexpectedChar(char),
+    /// A number appeared before any command.
+    MissingCommand,
+    /// The two paths do not have the same commands with the same number of coordinates.
+    MismatchedStructure,
+}
+
+/// Parses a simple SVG path into commands with their coordinates.
+fn parse_svg_path(path: &str) -> Result<Vec<(char, Vec<f64>)>, SvgParseError> {
+    let mut commands: Vec<(char, Vec<f64>)> = vec![];
+    let chars: Vec<char> = path.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() || c == ',' {
+            i += 1;
+        } else if c.is_ascii_alphabetic() && c != 'e' && c != 'E' {
+            commands.push((c, vec![]));
+            i += 1;
+        } else if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' {
+            let start = i;
+            // A second dot or a dot in the exponent starts a new number, e.g. `0.5.5`.
+            let mut seen_dot = c == '.';
+            let mut seen_exponent = false;
+            i += 1;
+            while i < chars.len() {
+                let d = chars[i];
+                let exponent_sign = (d == '-' || d == '+') &&
+                                    (chars[i - 1] == 'e' || chars[i - 1] == 'E');
+                if d == '.' && !seen_dot && !seen_exponent {
+                    seen_dot = true;
+                } else if (d == 'e' || d == 'E') && !seen_exponent {
+                    seen_exponent = true;
+                } else if !d.is_ascii_digit() && !exponent_sign {
+                    break;
+                }
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let val = text.parse().map_err(|_| SvgParseError::InvalidNumber(text.clone()))?;
+            match commands.last_mut() {
+                Some(&mut (_, ref mut args)) => args.push(val),
+                None => return Err(SvgParseError::MissingCommand),
+            }
+        } else {
+            return Err(SvgParseError::UnexpectedChar(c));
+        }
+    }
+    Ok(commands)
+}
+
+/// Blends two SVG paths with matching command structure.
+///
+/// Both paths must use the same sequence of commands with the same number of coordinates.
+/// The coordinates are interpolated linearly by `s` and the blended path is serialized
+/// with commands and numbers separated by spaces.
+pub fn blend_svg_paths(a: &str, b: &str, s: f64) -> Result<String, SvgParseError> {
+    let a = parse_svg_path(a)?;
+    let b = parse_svg_path(b)?;
+    if a.len() != b.len() {return Err(SvgParseError::MismatchedStructure)};
+    let mut res = String::new();
+    for (&(ca, ref va), &(cb, ref vb)) in a.iter().zip(b.iter()) {
+        if ca != cb || va.len() != vb.len() {return Err(SvgParseError::MismatchedStructure)};
+        if !res.is_empty() {res.push(' ')};
+        res.push(ca);
+        for (x, y) in va.iter().zip(vb.iter()) {
+            res.push_str(&format!(" {}", x * (1.0 - s) + y * s));
+        }
+    }
+    Ok(res)
+}
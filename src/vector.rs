@@ -0,0 +1,83 @@
+/// Implemented by outputs that can be added and scaled, such as points and vectors.
+///
+/// This is used by numerical methods that need to take differences of outputs.
+pub trait Vector: Sized {
+    /// Adds two values.
+    fn add_vec(self, other: Self) -> Self;
+    /// Subtracts another value.
+    fn sub_vec(self, other: Self) -> Self;
+    /// Scales by a factor.
+    fn scale(self, s: f64) -> Self;
+}
+
+impl Vector for f64 {
+    fn add_vec(self, other: f64) -> f64 {self + other}
+    fn sub_vec(self, other: f64) -> f64 {self - other}
+    fn scale(self, s: f64) -> f64 {self * s}
+}
+
+impl Vector for [f64; 2] {
+    fn add_vec(self, other: [f64; 2]) -> [f64; 2] {[self[0] + other[0], self[1] + other[1]]}
+    fn sub_vec(self, other: [f64; 2]) -> [f64; 2] {[self[0] - other[0], self[1] - other[1]]}
+    fn scale(self, s: f64) -> [f64; 2] {[self[0] * s, self[1] * s]}
+}
+
+impl Vector for [f64; 3] {
+    fn add_vec(self, other: [f64; 3]) -> [f64; 3] {
+        [self[0] + other[0], self[1] + other[1], self[2] + other[2]]
+    }
+    fn sub_vec(self, other: [f64; 3]) -> [f64; 3] {
+        [self[0] - other[0], self[1] - other[1], self[2] - other[2]]
+    }
+    fn scale(self, s: f64) -> [f64; 3] {[self[0] * s, self[1] * s, self[2] * s]}
+}
+
+impl Vector for [f64; 4] {
+    fn add_vec(self, other: [f64; 4]) -> [f64; 4] {
+        [self[0] + other[0], self[1] + other[1], self[2] + other[2], self[3] + other[3]]
+    }
+    fn sub_vec(self, other: [f64; 4]) -> [f64; 4] {
+        [self[0] - other[0], self[1] - other[1], self[2] - other[2], self[3] - other[3]]
+    }
+    fn scale(self, s: f64) -> [f64; 4] {[self[0] * s, self[1] * s, self[2] * s, self[3] * s]}
+}
+
+/// Implemented by values that can be interpolated, such as scalars, points and colors.
+///
+/// Arrays of `f64` cover points and vectors as well as colors, e.g. `[f64; 3]` for linear RGB
+/// and `[f64; 4]` for linear RGBA. Colors should be in linear space,
+/// since interpolating gamma-encoded colors gives too dark midpoints.
+pub trait Tween {
+    /// Interpolates from `self` to `other` by `s`.
+    fn tween(&self, other: &Self, s: f64) -> Self;
+}
+
+impl Tween for f64 {
+    fn tween(&self, other: &f64, s: f64) -> f64 {self * (1.0 - s) + other * s}
+}
+
+impl Tween for [f64; 2] {
+    fn tween(&self, other: &[f64; 2], s: f64) -> [f64; 2] {
+        self.scale(1.0 - s).add_vec(other.scale(s))
+    }
+}
+
+impl Tween for [f64; 3] {
+    fn tween(&self, other: &[f64; 3], s: f64) -> [f64; 3] {
+        self.scale(1.0 - s).add_vec(other.scale(s))
+    }
+}
+
+impl Tween for [f64; 4] {
+    fn tween(&self, other: &[f64; 4], s: f64) -> [f64; 4] {
+        self.scale(1.0 - s).add_vec(other.scale(s))
+    }
+}
+
+pub(crate) fn cross3(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]]
+}
+
+pub(crate) fn dot3(a: [f64; 3], b: [f64; 3]) -> f64 {a[0] * b[0] + a[1] * b[1] + a[2] * b[2]}
+
+pub(crate) fn normalize3(a: [f64; 3]) -> [f64; 3] {a.scale(1.0 / dot3(a, a).sqrt())}